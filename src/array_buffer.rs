@@ -1,17 +1,143 @@
+use std::cmp::min;
+use std::ffi::c_void;
+use std::mem::drop;
+use std::mem::forget;
+use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::ptr::copy_nonoverlapping;
+use std::ptr::null_mut;
+use std::ptr::write_bytes;
+use std::slice;
+
+use crate::isolate::Isolate;
+use crate::support::long;
+use crate::support::CxxVTable;
 use crate::support::Delete;
+use crate::support::FieldOffset;
 use crate::support::Opaque;
+use crate::support::RustVTable;
+use crate::support::Shared;
+use crate::support::SharedRef;
 use crate::support::UniqueRef;
+use crate::HandleScope;
+use crate::Local;
+use crate::Object;
+
+// class ArrayBuffer::Allocator {
+//  public:
+//   virtual ~Allocator() = default;
+//   virtual void* Allocate(size_t length) = 0;
+//   virtual void* AllocateUninitialized(size_t length) = 0;
+//   virtual void Free(void* data, size_t length) = 0;
+//   virtual void* Reallocate(void* data, size_t old_length,
+//                            size_t new_length);
+// };
 
 extern "C" {
   fn v8__ArrayBuffer__Allocator__NewDefaultAllocator() -> *mut Allocator;
   fn v8__ArrayBuffer__Allocator__DELETE(this: &'static mut Allocator);
+  fn v8__ArrayBuffer__Allocator__BASE__CONSTRUCT(
+    buf: &mut std::mem::MaybeUninit<Allocator>,
+  ) -> ();
+
+  fn v8__ArrayBuffer__New__with_byte_length(
+    isolate: *mut Isolate,
+    byte_length: usize,
+  ) -> *mut ArrayBuffer;
+  fn v8__ArrayBuffer__New__with_backing_store(
+    isolate: *mut Isolate,
+    backing_store: *const SharedRef<BackingStore>,
+  ) -> *mut ArrayBuffer;
+  fn v8__ArrayBuffer__ByteLength(this: *const ArrayBuffer) -> usize;
+  fn v8__ArrayBuffer__IsDetachable(this: *const ArrayBuffer) -> bool;
+  fn v8__ArrayBuffer__Detach(this: *mut ArrayBuffer);
+  fn v8__ArrayBuffer__GetBackingStore(
+    this: *mut ArrayBuffer,
+    out: &mut MaybeUninit<SharedRef<BackingStore>>,
+  );
+  fn v8__ArrayBuffer__NewBackingStore__with_byte_length(
+    isolate: *mut Isolate,
+    byte_length: usize,
+    out: &mut MaybeUninit<SharedRef<BackingStore>>,
+  );
+  fn v8__ArrayBuffer__NewBackingStore__with_data(
+    data: *mut c_void,
+    byte_length: usize,
+    deleter: BackingStoreDeleterCallback,
+    deleter_data: *mut c_void,
+    out: &mut MaybeUninit<SharedRef<BackingStore>>,
+  );
+
+  fn v8__BackingStore__Data(this: *const BackingStore) -> *mut c_void;
+  fn v8__BackingStore__ByteLength(this: *const BackingStore) -> usize;
+  fn v8__BackingStore__IsShared(this: *const BackingStore) -> bool;
+
+  fn std__shared_ptr__v8__BackingStore__COPY(
+    ptr: *const SharedRef<BackingStore>,
+    out: &mut MaybeUninit<SharedRef<BackingStore>>,
+  );
+  fn std__shared_ptr__v8__BackingStore__get(
+    ptr: *const SharedRef<BackingStore>,
+  ) -> *mut BackingStore;
+  fn std__shared_ptr__v8__BackingStore__reset(
+    ptr: *mut SharedRef<BackingStore>,
+  );
+  fn std__shared_ptr__v8__BackingStore__use_count(
+    ptr: *const SharedRef<BackingStore>,
+  ) -> long;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn v8__ArrayBuffer__Allocator__BASE__DELETE(
+  this: &mut Allocator,
+) {
+  drop(AllocatorBase::dispatch_box(this))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn v8__ArrayBuffer__Allocator__BASE__Allocate(
+  this: &Allocator,
+  length: usize,
+) -> *mut c_void {
+  AllocatorBase::dispatch(this).allocate(length)
 }
 
-// TODO: allow the user to implement their own Allocator.
+#[no_mangle]
+pub unsafe extern "C" fn v8__ArrayBuffer__Allocator__BASE__AllocateUninitialized(
+  this: &Allocator,
+  length: usize,
+) -> *mut c_void {
+  AllocatorBase::dispatch(this).allocate_uninitialized(length)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn v8__ArrayBuffer__Allocator__BASE__Free(
+  this: &Allocator,
+  data: *mut c_void,
+  length: usize,
+) {
+  AllocatorBase::dispatch(this).free(data, length)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn v8__ArrayBuffer__Allocator__BASE__Reallocate(
+  this: &Allocator,
+  data: *mut c_void,
+  old_length: usize,
+  new_length: usize,
+) -> *mut c_void {
+  AllocatorBase::dispatch(this).reallocate(data, old_length, new_length)
+}
+
+/// A thread-safe allocator that V8 uses to allocate the backing stores of
+/// ArrayBuffers.
 #[repr(C)]
-pub struct Allocator(Opaque);
+pub struct Allocator {
+  _cxx_vtable: CxxVTable,
+}
 
 impl Allocator {
+  /// malloc/free based convenience allocator.
   pub fn new_default_allocator() -> UniqueRef<Allocator> {
     unsafe {
       UniqueRef::from_raw(v8__ArrayBuffer__Allocator__NewDefaultAllocator())
@@ -25,12 +151,450 @@ impl Delete for Allocator {
   }
 }
 
+pub trait AsAllocator {
+  fn as_allocator(&self) -> &Allocator;
+  fn as_allocator_mut(&mut self) -> &mut Allocator;
+
+  fn into_unique_ref(mut self: Box<Self>) -> UniqueRef<Allocator>
+  where
+    Self: 'static,
+  {
+    let allocator = self.as_allocator_mut() as *mut Allocator;
+    forget(self);
+    unsafe { UniqueRef::from_raw(allocator) }
+  }
+}
+
+impl AsAllocator for Allocator {
+  fn as_allocator(&self) -> &Allocator {
+    self
+  }
+  fn as_allocator_mut(&mut self) -> &mut Allocator {
+    self
+  }
+}
+
+impl<T> AsAllocator for T
+where
+  T: AllocatorImpl,
+{
+  fn as_allocator(&self) -> &Allocator {
+    &self.base().cxx_base
+  }
+  fn as_allocator_mut(&mut self) -> &mut Allocator {
+    &mut self.base_mut().cxx_base
+  }
+}
+
+/// An ArrayBuffer allocator implemented in Rust.
+///
+/// V8 may call into the allocator from any thread, including its own
+/// background threads, which is why implementations must be `Send` and `Sync`
+/// and why the allocation methods take `&self`.
+pub trait AllocatorImpl: AsAllocator + Send + Sync {
+  fn base(&self) -> &AllocatorBase;
+  fn base_mut(&mut self) -> &mut AllocatorBase;
+
+  /// Allocate `length` bytes. Return null if allocation is not successful.
+  /// Memory should be initialized to zeroes.
+  fn allocate(&self, length: usize) -> *mut c_void;
+
+  /// Allocate `length` bytes. Return null if allocation is not successful.
+  /// Memory does not have to be initialized.
+  fn allocate_uninitialized(&self, length: usize) -> *mut c_void;
+
+  /// Free the memory block of size `length`, pointed to by `data`.
+  /// That memory is guaranteed to be previously allocated by `allocate()`.
+  fn free(&self, data: *mut c_void, length: usize) -> ();
+
+  /// Reallocate the memory block of size `old_length` to a memory block of
+  /// size `new_length` by expanding, contracting, or copying/moving the
+  /// memory block. If `new_length` > `old_length`, then the new part of the
+  /// memory must be initialized to zeros. Return null if reallocation is not
+  /// successful.
+  ///
+  /// The default implementation allocates a new block of memory and copies
+  /// data.
+  fn reallocate(
+    &self,
+    data: *mut c_void,
+    old_length: usize,
+    new_length: usize,
+  ) -> *mut c_void {
+    let new_data = self.allocate_uninitialized(new_length);
+    if new_data.is_null() {
+      return null_mut();
+    }
+    let bytes_to_copy = min(old_length, new_length);
+    unsafe {
+      copy_nonoverlapping(
+        data as *const u8,
+        new_data as *mut u8,
+        bytes_to_copy,
+      );
+      write_bytes(
+        (new_data as *mut u8).add(bytes_to_copy),
+        0,
+        new_length - bytes_to_copy,
+      );
+    }
+    self.free(data, old_length);
+    new_data
+  }
+}
+
+pub struct AllocatorBase {
+  cxx_base: Allocator,
+  offset_within_embedder: FieldOffset<Self>,
+  rust_vtable: RustVTable<&'static dyn AllocatorImpl>,
+}
+
+// The fields of AllocatorBase are only ever read, and the C++ base class has
+// no state other than its vtable pointer.
+unsafe impl Send for AllocatorBase {}
+unsafe impl Sync for AllocatorBase {}
+
+impl AllocatorBase {
+  fn construct_cxx_base() -> Allocator {
+    unsafe {
+      let mut buf = std::mem::MaybeUninit::<Allocator>::uninit();
+      v8__ArrayBuffer__Allocator__BASE__CONSTRUCT(&mut buf);
+      buf.assume_init()
+    }
+  }
+
+  fn get_cxx_base_offset() -> FieldOffset<Allocator> {
+    let buf = std::mem::MaybeUninit::<Self>::uninit();
+    FieldOffset::from_ptrs(buf.as_ptr(), unsafe { &(*buf.as_ptr()).cxx_base })
+  }
+
+  fn get_offset_within_embedder<T>() -> FieldOffset<Self>
+  where
+    T: AllocatorImpl,
+  {
+    let buf = std::mem::MaybeUninit::<T>::uninit();
+    let embedder_ptr: *const T = buf.as_ptr();
+    let self_ptr: *const Self = unsafe { (*embedder_ptr).base() };
+    FieldOffset::from_ptrs(embedder_ptr, self_ptr)
+  }
+
+  fn get_rust_vtable<T>() -> RustVTable<&'static dyn AllocatorImpl>
+  where
+    T: AllocatorImpl,
+  {
+    let buf = std::mem::MaybeUninit::<T>::uninit();
+    let embedder_ptr = buf.as_ptr();
+    let trait_object: *const dyn AllocatorImpl = embedder_ptr;
+    let (data_ptr, vtable): (*const T, RustVTable<_>) =
+      unsafe { std::mem::transmute(trait_object) };
+    assert_eq!(data_ptr, embedder_ptr);
+    vtable
+  }
+
+  pub fn new<T>() -> Self
+  where
+    T: AllocatorImpl,
+  {
+    Self {
+      cxx_base: Self::construct_cxx_base(),
+      offset_within_embedder: Self::get_offset_within_embedder::<T>(),
+      rust_vtable: Self::get_rust_vtable::<T>(),
+    }
+  }
+
+  pub unsafe fn dispatch(allocator: &Allocator) -> &dyn AllocatorImpl {
+    let this = Self::get_cxx_base_offset().to_embedder::<Self>(allocator);
+    let embedder = this.offset_within_embedder.to_embedder::<Opaque>(this);
+    std::mem::transmute((embedder, this.rust_vtable))
+  }
+
+  pub unsafe fn dispatch_mut(
+    allocator: &mut Allocator,
+  ) -> &mut dyn AllocatorImpl {
+    let this = Self::get_cxx_base_offset().to_embedder_mut::<Self>(allocator);
+    let vtable = this.rust_vtable;
+    let embedder = this.offset_within_embedder.to_embedder_mut::<Opaque>(this);
+    std::mem::transmute((embedder, vtable))
+  }
+
+  pub unsafe fn dispatch_box(
+    allocator: &mut Allocator,
+  ) -> Box<dyn AllocatorImpl> {
+    std::mem::transmute(Self::dispatch_mut(allocator))
+  }
+}
+
+/// Called when the last reference to a BackingStore that wraps embedder
+/// memory is dropped. Provided with arguments: (data, byte_length,
+/// deleter_data).
+pub type BackingStoreDeleterCallback = extern "C" fn(
+  data: *mut c_void,
+  byte_length: usize,
+  deleter_data: *mut c_void,
+);
+
+pub(crate) extern "C" fn boxed_slice_deleter_callback(
+  data: *mut c_void,
+  byte_length: usize,
+  _deleter_data: *mut c_void,
+) {
+  let slice: &mut [u8] =
+    unsafe { slice::from_raw_parts_mut(data as *mut u8, byte_length) };
+  drop(unsafe { Box::from_raw(slice) })
+}
+
+/// A wrapper around the backing store (i.e. the raw memory) of an array
+/// buffer.
+///
+/// The allocation and destruction of backing stores is generally managed by
+/// V8. Backing stores are reference counted through SharedRef, since V8
+/// internal objects may alias them.
+#[repr(C)]
+pub struct BackingStore(Opaque);
+
+impl BackingStore {
+  /// Return a pointer to the beginning of the memory block for this backing
+  /// store. The pointer is only valid as long as this backing store object
+  /// lives.
+  pub fn data(&self) -> *mut c_void {
+    unsafe { v8__BackingStore__Data(self) }
+  }
+
+  /// The length (in bytes) of this backing store.
+  pub fn byte_length(&self) -> usize {
+    unsafe { v8__BackingStore__ByteLength(self) }
+  }
+
+  /// Indicates whether the backing store was created for an ArrayBuffer or
+  /// a SharedArrayBuffer.
+  pub fn is_shared(&self) -> bool {
+    unsafe { v8__BackingStore__IsShared(self) }
+  }
+}
+
+impl Shared for BackingStore {
+  fn clone(ptr: &SharedRef<Self>) -> SharedRef<Self> {
+    let mut buf = MaybeUninit::uninit();
+    unsafe {
+      std__shared_ptr__v8__BackingStore__COPY(ptr, &mut buf);
+      buf.assume_init()
+    }
+  }
+  fn get(ptr: &SharedRef<Self>) -> *mut Self {
+    unsafe { std__shared_ptr__v8__BackingStore__get(ptr) }
+  }
+  fn reset(ptr: &mut SharedRef<Self>) {
+    unsafe { std__shared_ptr__v8__BackingStore__reset(ptr) }
+  }
+  fn use_count(ptr: &SharedRef<Self>) -> long {
+    unsafe { std__shared_ptr__v8__BackingStore__use_count(ptr) }
+  }
+}
+
+/// An instance of the built-in ArrayBuffer constructor (ES6 draft 15.13.5).
+#[repr(C)]
+pub struct ArrayBuffer(Opaque);
+
+impl ArrayBuffer {
+  /// Create a new ArrayBuffer. Allocate |byte_length| bytes.
+  /// Allocated memory will be owned by a created ArrayBuffer and
+  /// will be deallocated when it is garbage-collected,
+  /// unless the object is externalized.
+  pub fn new<'sc>(
+    scope: &mut HandleScope<'sc>,
+    byte_length: usize,
+  ) -> Local<'sc, ArrayBuffer> {
+    unsafe {
+      Local::from_raw(v8__ArrayBuffer__New__with_byte_length(
+        scope.as_mut(),
+        byte_length,
+      ))
+    }
+    .unwrap()
+  }
+
+  /// Create a new ArrayBuffer with an existing backing store.
+  /// The created array keeps a reference to the backing store until the array
+  /// is garbage collected. Note that the IsExternal bit does not affect this
+  /// reference from the array to the backing store.
+  pub fn new_with_backing_store<'sc>(
+    scope: &mut HandleScope<'sc>,
+    backing_store: &SharedRef<BackingStore>,
+  ) -> Local<'sc, ArrayBuffer> {
+    unsafe {
+      Local::from_raw(v8__ArrayBuffer__New__with_backing_store(
+        scope.as_mut(),
+        backing_store,
+      ))
+    }
+    .unwrap()
+  }
+
+  /// Data length in bytes.
+  pub fn byte_length(&self) -> usize {
+    unsafe { v8__ArrayBuffer__ByteLength(self) }
+  }
+
+  /// Returns true if this ArrayBuffer may be detached.
+  pub fn is_detachable(&self) -> bool {
+    unsafe { v8__ArrayBuffer__IsDetachable(self) }
+  }
+
+  /// Detaches this ArrayBuffer and all its views (typed arrays).
+  /// Detaching sets the byte length of the buffer and all typed arrays to zero,
+  /// preventing JavaScript from ever accessing underlying backing store.
+  /// ArrayBuffer should have been externalized and must be detachable.
+  pub fn detach(&mut self) {
+    assert!(self.is_detachable());
+    unsafe { v8__ArrayBuffer__Detach(self) }
+  }
+
+  /// Get a shared pointer to the backing store of this array buffer. This
+  /// pointer coordinates the lifetime management of the internal storage
+  /// with any live ArrayBuffers on the heap, even across isolates. The
+  /// embedder should not attempt to manage lifetime of the storage through
+  /// other means.
+  pub fn get_backing_store(&mut self) -> SharedRef<BackingStore> {
+    let mut buf = MaybeUninit::uninit();
+    unsafe {
+      v8__ArrayBuffer__GetBackingStore(self, &mut buf);
+      buf.assume_init()
+    }
+  }
+
+  /// Returns a new standalone BackingStore that is allocated using the array
+  /// buffer allocator of the isolate. The result can be later passed to
+  /// ArrayBuffer::new_with_backing_store().
+  pub fn new_backing_store(
+    scope: &mut HandleScope,
+    byte_length: usize,
+  ) -> SharedRef<BackingStore> {
+    let mut buf = MaybeUninit::uninit();
+    unsafe {
+      v8__ArrayBuffer__NewBackingStore__with_byte_length(
+        scope.as_mut(),
+        byte_length,
+        &mut buf,
+      );
+      buf.assume_init()
+    }
+  }
+
+  /// Returns a new standalone BackingStore that takes over the ownership of
+  /// the given buffer. The memory is freed when the last reference to the
+  /// backing store is dropped, which may happen on any thread.
+  ///
+  /// The result can be later passed to ArrayBuffer::new_with_backing_store().
+  pub fn new_backing_store_from_boxed_slice(
+    data: Box<[u8]>,
+  ) -> SharedRef<BackingStore> {
+    let byte_length = data.len();
+    let data_ptr = Box::into_raw(data) as *mut u8 as *mut c_void;
+    let mut buf = MaybeUninit::uninit();
+    unsafe {
+      v8__ArrayBuffer__NewBackingStore__with_data(
+        data_ptr,
+        byte_length,
+        boxed_slice_deleter_callback,
+        null_mut(),
+        &mut buf,
+      );
+      buf.assume_init()
+    }
+  }
+
+  /// Same as `new_backing_store_from_boxed_slice()`, but takes a Vec. Any
+  /// excess capacity of the Vec is released first.
+  pub fn new_backing_store_from_vec(data: Vec<u8>) -> SharedRef<BackingStore> {
+    Self::new_backing_store_from_boxed_slice(data.into_boxed_slice())
+  }
+}
+
+impl Deref for ArrayBuffer {
+  type Target = Object;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Object) }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
+  use std::sync::atomic::AtomicUsize;
+  use std::sync::atomic::Ordering::SeqCst;
 
   #[test]
   fn test_default_allocator() {
     Allocator::new_default_allocator();
   }
+
+  static DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  // Using repr(C) to preserve field ordering and test that everything works
+  // when the AllocatorBase field is not the first element of the struct.
+  #[repr(C)]
+  struct TestAllocator {
+    allocated: AtomicUsize,
+    base: AllocatorBase,
+  }
+
+  impl TestAllocator {
+    pub fn new() -> Self {
+      Self {
+        allocated: AtomicUsize::new(0),
+        base: AllocatorBase::new::<Self>(),
+      }
+    }
+  }
+
+  impl AllocatorImpl for TestAllocator {
+    fn base(&self) -> &AllocatorBase {
+      &self.base
+    }
+    fn base_mut(&mut self) -> &mut AllocatorBase {
+      &mut self.base
+    }
+    fn allocate(&self, length: usize) -> *mut c_void {
+      let data = self.allocate_uninitialized(length);
+      unsafe { write_bytes(data as *mut u8, 0, length) };
+      data
+    }
+    fn allocate_uninitialized(&self, length: usize) -> *mut c_void {
+      self.allocated.fetch_add(length, SeqCst);
+      let mut data = Vec::<u8>::with_capacity(length);
+      let ptr = data.as_mut_ptr();
+      forget(data);
+      ptr as *mut c_void
+    }
+    fn free(&self, data: *mut c_void, length: usize) {
+      self.allocated.fetch_sub(length, SeqCst);
+      drop(unsafe { Vec::from_raw_parts(data as *mut u8, 0, length) });
+    }
+  }
+
+  impl Drop for TestAllocator {
+    fn drop(&mut self) {
+      DROP_COUNT.fetch_add(1, SeqCst);
+    }
+  }
+
+  #[test]
+  fn test_allocator_impl() {
+    {
+      let allocator = TestAllocator::new();
+      let data = allocator.allocate(4);
+      let data = allocator.reallocate(data, 4, 8);
+      let bytes = unsafe { std::slice::from_raw_parts(data as *const u8, 8) };
+      assert_eq!(bytes, &[0u8; 8]);
+      assert_eq!(allocator.allocated.load(SeqCst), 8);
+      allocator.free(data, 8);
+      assert_eq!(allocator.allocated.load(SeqCst), 0);
+    }
+    assert_eq!(DROP_COUNT.swap(0, SeqCst), 1);
+
+    {
+      Box::new(TestAllocator::new()).into_unique_ref();
+    }
+    assert_eq!(DROP_COUNT.swap(0, SeqCst), 1);
+  }
 }
@@ -0,0 +1,173 @@
+use std::ffi::c_void;
+use std::ops::Deref;
+use std::slice;
+
+use crate::support::Opaque;
+use crate::support::SharedRef;
+use crate::ArrayBuffer;
+use crate::BackingStore;
+use crate::HandleScope;
+use crate::Local;
+use crate::Object;
+
+extern "C" {
+  fn v8__ArrayBufferView__Buffer(
+    this: *mut ArrayBufferView,
+  ) -> *mut ArrayBuffer;
+  fn v8__ArrayBufferView__ByteOffset(this: *const ArrayBufferView) -> usize;
+  fn v8__ArrayBufferView__ByteLength(this: *const ArrayBufferView) -> usize;
+  fn v8__ArrayBufferView__CopyContents(
+    this: *mut ArrayBufferView,
+    dest: *mut c_void,
+    byte_length: usize,
+  ) -> usize;
+  fn v8__DataView__New(
+    buffer: *mut ArrayBuffer,
+    byte_offset: usize,
+    length: usize,
+  ) -> *mut DataView;
+}
+
+/// A base class for an instance of one of "views" over ArrayBuffer,
+/// including TypedArrays and DataView (ES6 draft 15.13).
+#[repr(C)]
+pub struct ArrayBufferView(Opaque);
+
+impl ArrayBufferView {
+  /// Returns underlying ArrayBuffer.
+  pub fn buffer<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+  ) -> Option<Local<'sc, ArrayBuffer>> {
+    unsafe { Local::from_raw(v8__ArrayBufferView__Buffer(self.as_mut_ptr())) }
+  }
+
+  /// Byte offset in |Buffer|.
+  pub fn byte_offset(&self) -> usize {
+    unsafe { v8__ArrayBufferView__ByteOffset(self) }
+  }
+
+  /// Size of a view in bytes.
+  pub fn byte_length(&self) -> usize {
+    unsafe { v8__ArrayBufferView__ByteLength(self) }
+  }
+
+  /// Copy the contents of the ArrayBufferView's buffer to an embedder defined
+  /// memory without additional overhead that calling ArrayBufferView::Buffer
+  /// might incur.
+  /// Returns the number of bytes actually written.
+  pub fn copy_contents(&self, dest: &mut [u8]) -> usize {
+    unsafe {
+      v8__ArrayBufferView__CopyContents(
+        self.as_mut_ptr(),
+        dest.as_mut_ptr() as *mut c_void,
+        dest.len(),
+      )
+    }
+  }
+
+  /// Calls `f` with the bytes covered by this view, without copying them.
+  ///
+  /// The backing store is kept alive for the duration of the call, so the
+  /// bytes remain valid even if the buffer is detached or garbage collected in
+  /// the meantime. Returns None for views on a shared backing store, whose
+  /// contents may be modified by other threads at any time.
+  pub fn with_slice<R, F>(&self, scope: &mut HandleScope, f: F) -> Option<R>
+  where
+    F: FnOnce(&[u8]) -> R,
+  {
+    let (backing_store, data, len) = self.backing_store_range(scope)?;
+    let bytes = if len == 0 {
+      &[]
+    } else {
+      unsafe { slice::from_raw_parts(data, len) }
+    };
+    let result = f(bytes);
+    drop(backing_store);
+    Some(result)
+  }
+
+  /// Like `with_slice()`, but gives `f` mutable access to the bytes covered by
+  /// this view.
+  pub fn with_mut_slice<R, F>(&self, scope: &mut HandleScope, f: F) -> Option<R>
+  where
+    F: FnOnce(&mut [u8]) -> R,
+  {
+    let (backing_store, data, len) = self.backing_store_range(scope)?;
+    let bytes = if len == 0 {
+      &mut []
+    } else {
+      unsafe { slice::from_raw_parts_mut(data, len) }
+    };
+    let result = f(bytes);
+    drop(backing_store);
+    Some(result)
+  }
+
+  // Returns the backing store of the view's buffer, along with a pointer to
+  // the first byte of the view and its length. Calling buffer() moves the
+  // contents of an on-heap typed array to an off-heap backing store.
+  fn backing_store_range(
+    &self,
+    scope: &mut HandleScope,
+  ) -> Option<(SharedRef<BackingStore>, *mut u8, usize)> {
+    let mut buffer = self.buffer(scope)?;
+    let backing_store = buffer.get_backing_store();
+    if backing_store.is_shared() {
+      return None;
+    }
+    let offset = self.byte_offset();
+    let len = self.byte_length();
+    assert!(offset + len <= backing_store.byte_length());
+    let data = backing_store.data() as *mut u8;
+    let data = if len == 0 {
+      data
+    } else {
+      unsafe { data.add(offset) }
+    };
+    Some((backing_store, data, len))
+  }
+
+  // V8 takes a non-const pointer for methods that may allocate a handle,
+  // even though they don't modify the view itself.
+  fn as_mut_ptr(&self) -> *mut Self {
+    self as *const Self as *mut Self
+  }
+}
+
+impl Deref for ArrayBufferView {
+  type Target = Object;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Object) }
+  }
+}
+
+/// An instance of DataView constructor (ES6 draft 15.13.7).
+#[repr(C)]
+pub struct DataView(Opaque);
+
+impl DataView {
+  /// Creates a DataView over `byte_length` bytes of `buffer`, starting at
+  /// `byte_offset`. Returns None if the range does not fit in the buffer.
+  pub fn new<'sc>(
+    _scope: &mut HandleScope<'sc>,
+    mut buffer: Local<ArrayBuffer>,
+    byte_offset: usize,
+    byte_length: usize,
+  ) -> Option<Local<'sc, DataView>> {
+    let end = byte_offset.checked_add(byte_length)?;
+    if end > buffer.byte_length() {
+      return None;
+    }
+    unsafe {
+      Local::from_raw(v8__DataView__New(&mut *buffer, byte_offset, byte_length))
+    }
+  }
+}
+
+impl Deref for DataView {
+  type Target = ArrayBufferView;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const ArrayBufferView) }
+  }
+}
@@ -16,6 +16,12 @@ static_assert(sizeof(v8::ScriptOrigin) == sizeof(size_t) * 7,
 static_assert(sizeof(v8::HandleScope) == sizeof(size_t) * 3,
               "HandleScope size mismatch");
 
+static_assert(sizeof(v8::EscapableHandleScope) == sizeof(size_t) * 4,
+              "EscapableHandleScope size mismatch");
+
+static_assert(sizeof(std::shared_ptr<v8::BackingStore>) == sizeof(size_t) * 2,
+              "std::shared_ptr<v8::BackingStore> size mismatch");
+
 static_assert(sizeof(v8::PromiseRejectMessage) == sizeof(size_t) * 3,
               "PromiseRejectMessage size mismatch");
 
@@ -30,6 +36,9 @@ static_assert(sizeof(v8::ReturnValue<v8::Value>) == sizeof(size_t) * 1,
 static_assert(sizeof(v8::TryCatch) == sizeof(size_t) * 6,
               "TryCatch size mismatch");
 
+static_assert(sizeof(v8::Maybe<double>) == sizeof(size_t) * 2,
+              "Maybe<double> size mismatch");
+
 extern "C" {
 
 void v8__V8__SetFlagsFromCommandLine(int* argc, char** argv) {
@@ -66,6 +75,18 @@ void v8__Isolate__Enter(v8::Isolate* isolate) { isolate->Enter(); }
 
 void v8__Isolate__Exit(v8::Isolate* isolate) { isolate->Exit(); }
 
+v8::Isolate* v8__Isolate__GetCurrent() { return v8::Isolate::GetCurrent(); }
+
+void v8__Isolate__SetData(v8::Isolate* isolate, uint32_t slot, void* data) {
+  isolate->SetData(slot, data);
+}
+
+void* v8__Isolate__GetData(v8::Isolate* isolate, uint32_t slot) {
+  return isolate->GetData(slot);
+}
+
+bool v8__Isolate__IsInUse(v8::Isolate* isolate) { return isolate->IsInUse(); }
+
 void v8__Isolate__SetPromiseRejectCallback(v8::Isolate* isolate,
                                            v8::PromiseRejectCallback callback) {
   isolate->SetPromiseRejectCallback(callback);
@@ -100,6 +121,94 @@ void v8__Isolate__CreateParams__SET__array_buffer_allocator(
   self.array_buffer_allocator = value;
 }
 
+v8::ResourceConstraints* v8__Isolate__CreateParams__CONSTRAINTS(
+    v8::Isolate::CreateParams& self) {
+  return &self.constraints;
+}
+
+void v8__Isolate__CreateParams__SET__external_references(
+    v8::Isolate::CreateParams& self, const intptr_t* value) {
+  self.external_references = value;
+}
+
+void v8__Isolate__CreateParams__SET__counter_lookup_callback(
+    v8::Isolate::CreateParams& self, v8::CounterLookupCallback value) {
+  self.counter_lookup_callback = value;
+}
+
+void v8__Isolate__CreateParams__SET__create_histogram_callback(
+    v8::Isolate::CreateParams& self, v8::CreateHistogramCallback value) {
+  self.create_histogram_callback = value;
+}
+
+void v8__Isolate__CreateParams__SET__add_histogram_sample_callback(
+    v8::Isolate::CreateParams& self, v8::AddHistogramSampleCallback value) {
+  self.add_histogram_sample_callback = value;
+}
+
+void v8__Isolate__CreateParams__SET__allow_atomics_wait(
+    v8::Isolate::CreateParams& self, bool value) {
+  self.allow_atomics_wait = value;
+}
+
+void v8__Isolate__CreateParams__SET__only_terminate_in_safe_scope(
+    v8::Isolate::CreateParams& self, bool value) {
+  self.only_terminate_in_safe_scope = value;
+}
+
+void v8__ResourceConstraints__ConfigureDefaultsFromHeapSize(
+    v8::ResourceConstraints& self, size_t initial_heap_size_in_bytes,
+    size_t maximum_heap_size_in_bytes) {
+  self.ConfigureDefaultsFromHeapSize(initial_heap_size_in_bytes,
+                                     maximum_heap_size_in_bytes);
+}
+
+void v8__ResourceConstraints__ConfigureDefaults(v8::ResourceConstraints& self,
+                                                uint64_t physical_memory,
+                                                uint64_t virtual_memory_limit) {
+  self.ConfigureDefaults(physical_memory, virtual_memory_limit);
+}
+
+size_t v8__ResourceConstraints__max_old_generation_size_in_bytes(
+    const v8::ResourceConstraints& self) {
+  return self.max_old_generation_size_in_bytes();
+}
+
+void v8__ResourceConstraints__set_max_old_generation_size_in_bytes(
+    v8::ResourceConstraints& self, size_t limit) {
+  self.set_max_old_generation_size_in_bytes(limit);
+}
+
+size_t v8__ResourceConstraints__max_young_generation_size_in_bytes(
+    const v8::ResourceConstraints& self) {
+  return self.max_young_generation_size_in_bytes();
+}
+
+void v8__ResourceConstraints__set_max_young_generation_size_in_bytes(
+    v8::ResourceConstraints& self, size_t limit) {
+  self.set_max_young_generation_size_in_bytes(limit);
+}
+
+size_t v8__ResourceConstraints__initial_old_generation_size_in_bytes(
+    const v8::ResourceConstraints& self) {
+  return self.initial_old_generation_size_in_bytes();
+}
+
+void v8__ResourceConstraints__set_initial_old_generation_size_in_bytes(
+    v8::ResourceConstraints& self, size_t initial_size) {
+  self.set_initial_old_generation_size_in_bytes(initial_size);
+}
+
+size_t v8__ResourceConstraints__initial_young_generation_size_in_bytes(
+    const v8::ResourceConstraints& self) {
+  return self.initial_young_generation_size_in_bytes();
+}
+
+void v8__ResourceConstraints__set_initial_young_generation_size_in_bytes(
+    v8::ResourceConstraints& self, size_t initial_size) {
+  self.set_initial_young_generation_size_in_bytes(initial_size);
+}
+
 void v8__HandleScope__CONSTRUCT(uninit_t<v8::HandleScope>& buf,
                                 v8::Isolate* isolate) {
   construct_in_place<v8::HandleScope>(buf, isolate);
@@ -111,6 +220,20 @@ v8::Isolate* v8__HandleScope__GetIsolate(const v8::HandleScope& self) {
   return self.GetIsolate();
 }
 
+void v8__EscapableHandleScope__CONSTRUCT(
+    uninit_t<v8::EscapableHandleScope>& buf, v8::Isolate* isolate) {
+  construct_in_place<v8::EscapableHandleScope>(buf, isolate);
+}
+
+void v8__EscapableHandleScope__DESTRUCT(v8::EscapableHandleScope& self) {
+  self.~EscapableHandleScope();
+}
+
+v8::Value* v8__EscapableHandleScope__Escape(v8::EscapableHandleScope& self,
+                                            v8::Local<v8::Value> value) {
+  return local_to_ptr(self.Escape(value));
+}
+
 void v8__Locker__CONSTRUCT(uninit_t<v8::Locker>& buf, v8::Isolate* isolate) {
   construct_in_place<v8::Locker>(buf, isolate);
 }
@@ -141,6 +264,61 @@ v8::Module* v8__ScriptCompiler__CompileModule(
   }
 }
 
+v8::Module::Status v8__Module__GetStatus(const v8::Module& self) {
+  return self.GetStatus();
+}
+
+v8::Value* v8__Module__GetException(const v8::Module& self) {
+  return local_to_ptr(self.GetException());
+}
+
+int v8__Module__GetModuleRequestsLength(const v8::Module& self) {
+  return self.GetModuleRequestsLength();
+}
+
+v8::String* v8__Module__GetModuleRequest(const v8::Module& self, int i) {
+  return local_to_ptr(self.GetModuleRequest(i));
+}
+
+int v8__Module__GetIdentityHash(const v8::Module& self) {
+  return self.GetIdentityHash();
+}
+
+// The Rust equivalent of v8::Module::ResolveCallback. It returns a raw
+// pointer, since a MaybeLocal<Module> can't be returned through the C ABI.
+typedef v8::Module* (*v8__Module__ResolveCallback)(
+    v8::Local<v8::Context> context, v8::Local<v8::String> specifier,
+    v8::Local<v8::Module> referrer);
+
+// V8 doesn't pass any embedder data to the ResolveCallback, so the Rust
+// callback is kept in a thread local for the duration of InstantiateModule().
+static thread_local v8__Module__ResolveCallback resolve_callback = nullptr;
+
+static v8::MaybeLocal<v8::Module> v8__Module__ResolveCallback__TRAMPOLINE(
+    v8::Local<v8::Context> context, v8::Local<v8::String> specifier,
+    v8::Local<v8::Module> referrer) {
+  assert(resolve_callback != nullptr);
+  return ptr_to_maybe_local(resolve_callback(context, specifier, referrer));
+}
+
+MaybeBool v8__Module__InstantiateModule(v8::Module& self,
+                                        v8::Local<v8::Context> context,
+                                        v8__Module__ResolveCallback callback) {
+  // Save the previous callback, InstantiateModule() may be called
+  // recursively from within a ResolveCallback.
+  auto prev_callback = resolve_callback;
+  resolve_callback = callback;
+  auto result = maybe_to_maybe_bool(self.InstantiateModule(
+      context, v8__Module__ResolveCallback__TRAMPOLINE));
+  resolve_callback = prev_callback;
+  return result;
+}
+
+v8::Value* v8__Module__Evaluate(v8::Module& self,
+                                v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.Evaluate(context));
+}
+
 bool v8__Value__IsUndefined(const v8::Value& self) {
   return self.IsUndefined();
 }
@@ -151,6 +329,255 @@ bool v8__Value__IsNullOrUndefined(const v8::Value& self) {
   return self.IsNullOrUndefined();
 }
 
+bool v8__Value__IsTrue(const v8::Value& self) { return self.IsTrue(); }
+
+bool v8__Value__IsFalse(const v8::Value& self) { return self.IsFalse(); }
+
+bool v8__Value__IsName(const v8::Value& self) { return self.IsName(); }
+
+bool v8__Value__IsString(const v8::Value& self) { return self.IsString(); }
+
+bool v8__Value__IsSymbol(const v8::Value& self) { return self.IsSymbol(); }
+
+bool v8__Value__IsFunction(const v8::Value& self) { return self.IsFunction(); }
+
+bool v8__Value__IsArray(const v8::Value& self) { return self.IsArray(); }
+
+bool v8__Value__IsObject(const v8::Value& self) { return self.IsObject(); }
+
+bool v8__Value__IsBigInt(const v8::Value& self) { return self.IsBigInt(); }
+
+bool v8__Value__IsBoolean(const v8::Value& self) { return self.IsBoolean(); }
+
+bool v8__Value__IsNumber(const v8::Value& self) { return self.IsNumber(); }
+
+bool v8__Value__IsExternal(const v8::Value& self) { return self.IsExternal(); }
+
+bool v8__Value__IsInt32(const v8::Value& self) { return self.IsInt32(); }
+
+bool v8__Value__IsUint32(const v8::Value& self) { return self.IsUint32(); }
+
+bool v8__Value__IsDate(const v8::Value& self) { return self.IsDate(); }
+
+bool v8__Value__IsArgumentsObject(const v8::Value& self) {
+  return self.IsArgumentsObject();
+}
+
+bool v8__Value__IsBigIntObject(const v8::Value& self) {
+  return self.IsBigIntObject();
+}
+
+bool v8__Value__IsBooleanObject(const v8::Value& self) {
+  return self.IsBooleanObject();
+}
+
+bool v8__Value__IsNumberObject(const v8::Value& self) {
+  return self.IsNumberObject();
+}
+
+bool v8__Value__IsStringObject(const v8::Value& self) {
+  return self.IsStringObject();
+}
+
+bool v8__Value__IsSymbolObject(const v8::Value& self) {
+  return self.IsSymbolObject();
+}
+
+bool v8__Value__IsNativeError(const v8::Value& self) {
+  return self.IsNativeError();
+}
+
+bool v8__Value__IsRegExp(const v8::Value& self) { return self.IsRegExp(); }
+
+bool v8__Value__IsAsyncFunction(const v8::Value& self) {
+  return self.IsAsyncFunction();
+}
+
+bool v8__Value__IsGeneratorFunction(const v8::Value& self) {
+  return self.IsGeneratorFunction();
+}
+
+bool v8__Value__IsGeneratorObject(const v8::Value& self) {
+  return self.IsGeneratorObject();
+}
+
+bool v8__Value__IsPromise(const v8::Value& self) { return self.IsPromise(); }
+
+bool v8__Value__IsMap(const v8::Value& self) { return self.IsMap(); }
+
+bool v8__Value__IsSet(const v8::Value& self) { return self.IsSet(); }
+
+bool v8__Value__IsMapIterator(const v8::Value& self) {
+  return self.IsMapIterator();
+}
+
+bool v8__Value__IsSetIterator(const v8::Value& self) {
+  return self.IsSetIterator();
+}
+
+bool v8__Value__IsWeakMap(const v8::Value& self) { return self.IsWeakMap(); }
+
+bool v8__Value__IsWeakSet(const v8::Value& self) { return self.IsWeakSet(); }
+
+bool v8__Value__IsArrayBuffer(const v8::Value& self) {
+  return self.IsArrayBuffer();
+}
+
+bool v8__Value__IsArrayBufferView(const v8::Value& self) {
+  return self.IsArrayBufferView();
+}
+
+bool v8__Value__IsTypedArray(const v8::Value& self) {
+  return self.IsTypedArray();
+}
+
+bool v8__Value__IsUint8Array(const v8::Value& self) {
+  return self.IsUint8Array();
+}
+
+bool v8__Value__IsUint8ClampedArray(const v8::Value& self) {
+  return self.IsUint8ClampedArray();
+}
+
+bool v8__Value__IsInt8Array(const v8::Value& self) {
+  return self.IsInt8Array();
+}
+
+bool v8__Value__IsUint16Array(const v8::Value& self) {
+  return self.IsUint16Array();
+}
+
+bool v8__Value__IsInt16Array(const v8::Value& self) {
+  return self.IsInt16Array();
+}
+
+bool v8__Value__IsUint32Array(const v8::Value& self) {
+  return self.IsUint32Array();
+}
+
+bool v8__Value__IsInt32Array(const v8::Value& self) {
+  return self.IsInt32Array();
+}
+
+bool v8__Value__IsFloat32Array(const v8::Value& self) {
+  return self.IsFloat32Array();
+}
+
+bool v8__Value__IsFloat64Array(const v8::Value& self) {
+  return self.IsFloat64Array();
+}
+
+bool v8__Value__IsBigInt64Array(const v8::Value& self) {
+  return self.IsBigInt64Array();
+}
+
+bool v8__Value__IsBigUint64Array(const v8::Value& self) {
+  return self.IsBigUint64Array();
+}
+
+bool v8__Value__IsDataView(const v8::Value& self) { return self.IsDataView(); }
+
+bool v8__Value__IsSharedArrayBuffer(const v8::Value& self) {
+  return self.IsSharedArrayBuffer();
+}
+
+bool v8__Value__IsProxy(const v8::Value& self) { return self.IsProxy(); }
+
+bool v8__Value__IsWebAssemblyCompiledModule(const v8::Value& self) {
+  return self.IsWebAssemblyCompiledModule();
+}
+
+bool v8__Value__IsModuleNamespaceObject(const v8::Value& self) {
+  return self.IsModuleNamespaceObject();
+}
+
+v8::String* v8__Value__ToString(const v8::Value& self,
+                                v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.ToString(context));
+}
+
+v8::String* v8__Value__ToDetailString(const v8::Value& self,
+                                      v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.ToDetailString(context));
+}
+
+v8::Number* v8__Value__ToNumber(const v8::Value& self,
+                                v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.ToNumber(context));
+}
+
+v8::Integer* v8__Value__ToInteger(const v8::Value& self,
+                                  v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.ToInteger(context));
+}
+
+v8::Uint32* v8__Value__ToUint32(const v8::Value& self,
+                                v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.ToUint32(context));
+}
+
+v8::Int32* v8__Value__ToInt32(const v8::Value& self,
+                              v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.ToInt32(context));
+}
+
+v8::Object* v8__Value__ToObject(const v8::Value& self,
+                                v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.ToObject(context));
+}
+
+v8::Boolean* v8__Value__ToBoolean(const v8::Value& self,
+                                  v8::Isolate* isolate) {
+  return local_to_ptr(self.ToBoolean(isolate));
+}
+
+bool v8__Value__BooleanValue(const v8::Value& self, v8::Isolate* isolate) {
+  return self.BooleanValue(isolate);
+}
+
+void v8__Value__NumberValue(const v8::Value& self,
+                            v8::Local<v8::Context> context,
+                            v8::Maybe<double>* out) {
+  *out = self.NumberValue(context);
+}
+
+void v8__Value__IntegerValue(const v8::Value& self,
+                             v8::Local<v8::Context> context,
+                             v8::Maybe<int64_t>* out) {
+  *out = self.IntegerValue(context);
+}
+
+void v8__Value__Uint32Value(const v8::Value& self,
+                            v8::Local<v8::Context> context,
+                            v8::Maybe<uint32_t>* out) {
+  *out = self.Uint32Value(context);
+}
+
+void v8__Value__Int32Value(const v8::Value& self,
+                           v8::Local<v8::Context> context,
+                           v8::Maybe<int32_t>* out) {
+  *out = self.Int32Value(context);
+}
+
+MaybeBool v8__Value__Equals(const v8::Value& self,
+                            v8::Local<v8::Context> context,
+                            v8::Local<v8::Value> that) {
+  return maybe_to_maybe_bool(self.Equals(context, that));
+}
+
+bool v8__Value__StrictEquals(const v8::Value& self,
+                             v8::Local<v8::Value> that) {
+  return self.StrictEquals(that);
+}
+
+bool v8__Value__SameValue(const v8::Value& self, v8::Local<v8::Value> that) {
+  return self.SameValue(that);
+}
+
+v8::String* v8__Value__TypeOf(v8::Value& self, v8::Isolate* isolate) {
+  return local_to_ptr(self.TypeOf(isolate));
+}
+
 v8::Primitive* v8__Null(v8::Isolate* isolate) {
   return local_to_ptr(v8::Null(isolate));
 }
@@ -197,6 +624,170 @@ v8::Isolate* v8__Object__GetIsolate(v8::Object& self) {
   return self.GetIsolate();
 }
 
+v8::Value* v8__Object__Get(v8::Object& self, v8::Local<v8::Context> context,
+                           v8::Local<v8::Value> key) {
+  return maybe_local_to_ptr(self.Get(context, key));
+}
+
+v8::Value* v8__Object__GetIndex(v8::Object& self,
+                                v8::Local<v8::Context> context,
+                                uint32_t index) {
+  return maybe_local_to_ptr(self.Get(context, index));
+}
+
+MaybeBool v8__Object__Set(v8::Object& self, v8::Local<v8::Context> context,
+                          v8::Local<v8::Value> key,
+                          v8::Local<v8::Value> value) {
+  return maybe_to_maybe_bool(self.Set(context, key, value));
+}
+
+MaybeBool v8__Object__SetIndex(v8::Object& self,
+                               v8::Local<v8::Context> context, uint32_t index,
+                               v8::Local<v8::Value> value) {
+  return maybe_to_maybe_bool(self.Set(context, index, value));
+}
+
+MaybeBool v8__Object__Has(v8::Object& self, v8::Local<v8::Context> context,
+                          v8::Local<v8::Value> key) {
+  return maybe_to_maybe_bool(self.Has(context, key));
+}
+
+MaybeBool v8__Object__HasOwnProperty(v8::Object& self,
+                                     v8::Local<v8::Context> context,
+                                     v8::Local<v8::Name> key) {
+  return maybe_to_maybe_bool(self.HasOwnProperty(context, key));
+}
+
+MaybeBool v8__Object__Delete(v8::Object& self, v8::Local<v8::Context> context,
+                             v8::Local<v8::Value> key) {
+  return maybe_to_maybe_bool(self.Delete(context, key));
+}
+
+MaybeBool v8__Object__CreateDataProperty(v8::Object& self,
+                                         v8::Local<v8::Context> context,
+                                         v8::Local<v8::Name> key,
+                                         v8::Local<v8::Value> value) {
+  return maybe_to_maybe_bool(self.CreateDataProperty(context, key, value));
+}
+
+MaybeBool v8__Object__DefineOwnProperty(v8::Object& self,
+                                        v8::Local<v8::Context> context,
+                                        v8::Local<v8::Name> key,
+                                        v8::Local<v8::Value> value,
+                                        v8::PropertyAttribute attr) {
+  return maybe_to_maybe_bool(
+      self.DefineOwnProperty(context, key, value, attr));
+}
+
+v8::Array* v8__Object__GetPropertyNames(v8::Object& self,
+                                        v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.GetPropertyNames(context));
+}
+
+v8::Array* v8__Object__GetOwnPropertyNames(v8::Object& self,
+                                           v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.GetOwnPropertyNames(context));
+}
+
+v8::Value* v8__Object__GetPrototype(v8::Object& self) {
+  return local_to_ptr(self.GetPrototype());
+}
+
+MaybeBool v8__Object__SetPrototype(v8::Object& self,
+                                   v8::Local<v8::Context> context,
+                                   v8::Local<v8::Value> prototype) {
+  return maybe_to_maybe_bool(self.SetPrototype(context, prototype));
+}
+
+MaybeBool v8__Object__SetAccessor(v8::Object& self,
+                                  v8::Local<v8::Context> context,
+                                  v8::Local<v8::Name> key,
+                                  v8::AccessorNameGetterCallback getter,
+                                  v8::AccessorNameSetterCallback setter,
+                                  v8::Value* data) {
+  return maybe_to_maybe_bool(self.SetAccessor(context, key, getter, setter,
+                                              ptr_to_maybe_local(data)));
+}
+
+int v8__Object__InternalFieldCount(v8::Object& self) {
+  return self.InternalFieldCount();
+}
+
+v8::Value* v8__Object__GetInternalField(v8::Object& self, int index) {
+  return local_to_ptr(self.GetInternalField(index));
+}
+
+void v8__Object__SetInternalField(v8::Object& self, int index,
+                                  v8::Local<v8::Value> value) {
+  self.SetInternalField(index, value);
+}
+
+void* v8__Object__GetAlignedPointerFromInternalField(v8::Object& self,
+                                                     int index) {
+  return self.GetAlignedPointerFromInternalField(index);
+}
+
+void v8__Object__SetAlignedPointerInInternalField(v8::Object& self, int index,
+                                                  void* value) {
+  self.SetAlignedPointerInInternalField(index, value);
+}
+
+v8::Array* v8__Array__New(v8::Isolate* isolate, int length) {
+  return local_to_ptr(v8::Array::New(isolate, length));
+}
+
+v8::Array* v8__Array__New__with_elements(v8::Isolate* isolate,
+                                         v8::Local<v8::Value>* elements,
+                                         size_t length) {
+  return local_to_ptr(v8::Array::New(isolate, elements, length));
+}
+
+uint32_t v8__Array__Length(const v8::Array& self) { return self.Length(); }
+
+v8::External* v8__External__New(v8::Isolate* isolate, void* value) {
+  return local_to_ptr(v8::External::New(isolate, value));
+}
+
+void* v8__External__Value(const v8::External& self) { return self.Value(); }
+
+v8::Value* v8__Global__New(v8::Isolate* isolate, v8::Value* other) {
+  auto global = v8::Global<v8::Value>(isolate, ptr_to_local(other));
+  return global_to_ptr(global);
+}
+
+v8::Value* v8__Global__Get(v8::Value* self, v8::Isolate* isolate) {
+  return local_to_ptr(ptr_to_global(self).Get(isolate));
+}
+
+void v8__Global__Reset(v8::Value* self) { ptr_to_global(self).Reset(); }
+
+// Implemented in Rust.
+void v8__Weak__FirstPassCallback(void* parameter);
+void v8__Weak__SecondPassCallback(void* parameter, v8::Isolate* isolate);
+
+static void v8__Weak__SecondPassCallback__TRAMPOLINE(
+    const v8::WeakCallbackInfo<void>& info) {
+  v8__Weak__SecondPassCallback(info.GetParameter(), info.GetIsolate());
+}
+
+// The first pass callback must reset the handle and is not allowed to call
+// into V8 otherwise, so the finalizer runs in the second pass.
+static void v8__Weak__FirstPassCallback__TRAMPOLINE(
+    const v8::WeakCallbackInfo<void>& info) {
+  v8__Weak__FirstPassCallback(info.GetParameter());
+  info.SetSecondPassCallback(v8__Weak__SecondPassCallback__TRAMPOLINE);
+}
+
+void v8__Global__SetWeak(v8::Value* self, void* parameter) {
+  ptr_to_global(self).SetWeak(parameter,
+                              v8__Weak__FirstPassCallback__TRAMPOLINE,
+                              v8::WeakCallbackType::kParameter);
+}
+
+void* v8__Global__ClearWeak(v8::Value* self) {
+  return ptr_to_global(self).ClearWeak<void>();
+}
+
 v8::Number* v8__Number__New(v8::Isolate* isolate, double value) {
   return *v8::Number::New(isolate, value);
 }
@@ -214,6 +805,10 @@ v8::Integer* v8__Integer__NewFromUnsigned(v8::Isolate* isolate,
 
 int64_t v8__Integer__Value(const v8::Integer& self) { return self.Value(); }
 
+int32_t v8__Int32__Value(const v8::Int32& self) { return self.Value(); }
+
+uint32_t v8__Uint32__Value(const v8::Uint32& self) { return self.Value(); }
+
 v8::ArrayBuffer::Allocator* v8__ArrayBuffer__Allocator__NewDefaultAllocator() {
   return v8::ArrayBuffer::Allocator::NewDefaultAllocator();
 }
@@ -222,6 +817,205 @@ void v8__ArrayBuffer__Allocator__DELETE(v8::ArrayBuffer::Allocator& self) {
   delete &self;
 }
 
+void v8__ArrayBuffer__Allocator__BASE__DELETE(
+    v8::ArrayBuffer::Allocator& self);
+void* v8__ArrayBuffer__Allocator__BASE__Allocate(
+    v8::ArrayBuffer::Allocator& self, size_t length);
+void* v8__ArrayBuffer__Allocator__BASE__AllocateUninitialized(
+    v8::ArrayBuffer::Allocator& self, size_t length);
+void v8__ArrayBuffer__Allocator__BASE__Free(v8::ArrayBuffer::Allocator& self,
+                                            void* data, size_t length);
+void* v8__ArrayBuffer__Allocator__BASE__Reallocate(
+    v8::ArrayBuffer::Allocator& self, void* data, size_t old_length,
+    size_t new_length);
+
+struct v8__ArrayBuffer__Allocator__BASE : public v8::ArrayBuffer::Allocator {
+  using Allocator::Allocator;
+  void operator delete(void* ptr) noexcept {
+    v8__ArrayBuffer__Allocator__BASE__DELETE(
+        *reinterpret_cast<v8::ArrayBuffer::Allocator*>(ptr));
+  }
+  void* Allocate(size_t length) override {
+    return v8__ArrayBuffer__Allocator__BASE__Allocate(*this, length);
+  }
+  void* AllocateUninitialized(size_t length) override {
+    return v8__ArrayBuffer__Allocator__BASE__AllocateUninitialized(*this,
+                                                                   length);
+  }
+  void Free(void* data, size_t length) override {
+    v8__ArrayBuffer__Allocator__BASE__Free(*this, data, length);
+  }
+  void* Reallocate(void* data, size_t old_length, size_t new_length) override {
+    return v8__ArrayBuffer__Allocator__BASE__Reallocate(*this, data, old_length,
+                                                        new_length);
+  }
+};
+
+static_assert(sizeof(v8__ArrayBuffer__Allocator__BASE) == sizeof(size_t),
+              "ArrayBuffer::Allocator size mismatch");
+
+void v8__ArrayBuffer__Allocator__BASE__CONSTRUCT(
+    uninit_t<v8__ArrayBuffer__Allocator__BASE>& buf) {
+  construct_in_place<v8__ArrayBuffer__Allocator__BASE>(buf);
+}
+
+v8::ArrayBuffer* v8__ArrayBuffer__New__with_byte_length(v8::Isolate* isolate,
+                                                       size_t byte_length) {
+  return local_to_ptr(v8::ArrayBuffer::New(isolate, byte_length));
+}
+
+v8::ArrayBuffer* v8__ArrayBuffer__New__with_backing_store(
+    v8::Isolate* isolate,
+    const std::shared_ptr<v8::BackingStore>& backing_store) {
+  return local_to_ptr(v8::ArrayBuffer::New(isolate, backing_store));
+}
+
+size_t v8__ArrayBuffer__ByteLength(const v8::ArrayBuffer& self) {
+  return self.ByteLength();
+}
+
+bool v8__ArrayBuffer__IsDetachable(const v8::ArrayBuffer& self) {
+  return self.IsDetachable();
+}
+
+void v8__ArrayBuffer__Detach(v8::ArrayBuffer& self) { self.Detach(); }
+
+void v8__ArrayBuffer__GetBackingStore(
+    v8::ArrayBuffer& self, uninit_t<std::shared_ptr<v8::BackingStore>>& out) {
+  construct_in_place<std::shared_ptr<v8::BackingStore>>(
+      out, self.GetBackingStore());
+}
+
+void v8__ArrayBuffer__NewBackingStore__with_byte_length(
+    v8::Isolate* isolate, size_t byte_length,
+    uninit_t<std::shared_ptr<v8::BackingStore>>& out) {
+  construct_in_place<std::shared_ptr<v8::BackingStore>>(
+      out, v8::ArrayBuffer::NewBackingStore(isolate, byte_length));
+}
+
+void v8__ArrayBuffer__NewBackingStore__with_data(
+    void* data, size_t byte_length, v8::BackingStoreDeleterCallback deleter,
+    void* deleter_data, uninit_t<std::shared_ptr<v8::BackingStore>>& out) {
+  construct_in_place<std::shared_ptr<v8::BackingStore>>(
+      out, v8::ArrayBuffer::NewBackingStore(data, byte_length, deleter,
+                                            deleter_data));
+}
+
+v8::SharedArrayBuffer* v8__SharedArrayBuffer__New__with_byte_length(
+    v8::Isolate* isolate, size_t byte_length) {
+  return local_to_ptr(v8::SharedArrayBuffer::New(isolate, byte_length));
+}
+
+v8::SharedArrayBuffer* v8__SharedArrayBuffer__New__with_backing_store(
+    v8::Isolate* isolate,
+    const std::shared_ptr<v8::BackingStore>& backing_store) {
+  return local_to_ptr(v8::SharedArrayBuffer::New(isolate, backing_store));
+}
+
+size_t v8__SharedArrayBuffer__ByteLength(const v8::SharedArrayBuffer& self) {
+  return self.ByteLength();
+}
+
+void v8__SharedArrayBuffer__GetBackingStore(
+    v8::SharedArrayBuffer& self,
+    uninit_t<std::shared_ptr<v8::BackingStore>>& out) {
+  construct_in_place<std::shared_ptr<v8::BackingStore>>(
+      out, self.GetBackingStore());
+}
+
+void v8__SharedArrayBuffer__NewBackingStore__with_byte_length(
+    v8::Isolate* isolate, size_t byte_length,
+    uninit_t<std::shared_ptr<v8::BackingStore>>& out) {
+  construct_in_place<std::shared_ptr<v8::BackingStore>>(
+      out, v8::SharedArrayBuffer::NewBackingStore(isolate, byte_length));
+}
+
+void v8__SharedArrayBuffer__NewBackingStore__with_data(
+    void* data, size_t byte_length, v8::BackingStoreDeleterCallback deleter,
+    void* deleter_data, uninit_t<std::shared_ptr<v8::BackingStore>>& out) {
+  construct_in_place<std::shared_ptr<v8::BackingStore>>(
+      out, v8::SharedArrayBuffer::NewBackingStore(data, byte_length, deleter,
+                                                  deleter_data));
+}
+
+v8::ArrayBuffer* v8__ArrayBufferView__Buffer(v8::ArrayBufferView& self) {
+  return local_to_ptr(self.Buffer());
+}
+
+size_t v8__ArrayBufferView__ByteOffset(const v8::ArrayBufferView& self) {
+  return self.ByteOffset();
+}
+
+size_t v8__ArrayBufferView__ByteLength(const v8::ArrayBufferView& self) {
+  return self.ByteLength();
+}
+
+size_t v8__ArrayBufferView__CopyContents(v8::ArrayBufferView& self,
+                                         void* dest, size_t byte_length) {
+  return self.CopyContents(dest, byte_length);
+}
+
+size_t v8__TypedArray__Length(v8::TypedArray& self) { return self.Length(); }
+
+v8::Uint8Array* v8__Uint8Array__New(v8::Local<v8::ArrayBuffer> buffer,
+                                    size_t byte_offset, size_t length) {
+  return local_to_ptr(v8::Uint8Array::New(buffer, byte_offset, length));
+}
+
+v8::Int32Array* v8__Int32Array__New(v8::Local<v8::ArrayBuffer> buffer,
+                                    size_t byte_offset, size_t length) {
+  return local_to_ptr(v8::Int32Array::New(buffer, byte_offset, length));
+}
+
+v8::Float64Array* v8__Float64Array__New(v8::Local<v8::ArrayBuffer> buffer,
+                                        size_t byte_offset, size_t length) {
+  return local_to_ptr(v8::Float64Array::New(buffer, byte_offset, length));
+}
+
+v8::BigInt64Array* v8__BigInt64Array__New(v8::Local<v8::ArrayBuffer> buffer,
+                                          size_t byte_offset, size_t length) {
+  return local_to_ptr(v8::BigInt64Array::New(buffer, byte_offset, length));
+}
+
+v8::DataView* v8__DataView__New(v8::Local<v8::ArrayBuffer> buffer,
+                                size_t byte_offset, size_t length) {
+  return local_to_ptr(v8::DataView::New(buffer, byte_offset, length));
+}
+
+void* v8__BackingStore__Data(const v8::BackingStore& self) {
+  return self.Data();
+}
+
+size_t v8__BackingStore__ByteLength(const v8::BackingStore& self) {
+  return self.ByteLength();
+}
+
+bool v8__BackingStore__IsShared(const v8::BackingStore& self) {
+  return self.IsShared();
+}
+
+void std__shared_ptr__v8__BackingStore__COPY(
+    const std::shared_ptr<v8::BackingStore>& ptr,
+    uninit_t<std::shared_ptr<v8::BackingStore>>& out) {
+  construct_in_place<std::shared_ptr<v8::BackingStore>>(out, ptr);
+}
+
+v8::BackingStore* std__shared_ptr__v8__BackingStore__get(
+    const std::shared_ptr<v8::BackingStore>& ptr) {
+  return ptr.get();
+}
+
+void std__shared_ptr__v8__BackingStore__reset(
+    std::shared_ptr<v8::BackingStore>& ptr) {
+  using shared_ptr_t = std::shared_ptr<v8::BackingStore>;
+  ptr.~shared_ptr_t();
+}
+
+long std__shared_ptr__v8__BackingStore__use_count(
+    const std::shared_ptr<v8::BackingStore>& ptr) {
+  return ptr.use_count();
+}
+
 v8::Context* v8__Context__New(v8::Isolate* isolate) {
   // TODO: optional arguments.
   return *v8::Context::New(isolate);
@@ -275,26 +1069,160 @@ v8::StackTrace* v8__Exception__GetStackTrace(v8::Local<v8::Value> exception) {
 }
 
 v8::Function* v8__Function__New(v8::Local<v8::Context> context,
-                                v8::FunctionCallback callback) {
-  return maybe_local_to_ptr(v8::Function::New(context, callback));
+                                v8::FunctionCallback callback,
+                                v8::Value* data) {
+  return maybe_local_to_ptr(
+      v8::Function::New(context, callback, ptr_to_local(data)));
 }
 
-v8::Value* v8__Function__Call(v8::Function* self,
+v8::Value* v8__Function__Call(v8::Function& self,
                               v8::Local<v8::Context> context,
                               v8::Local<v8::Value> recv, int argc,
-                              v8::Local<v8::Value> argv[]) {
-  return maybe_local_to_ptr(self->Call(context, recv, argc, argv));
+                              v8::Local<v8::Value>* argv) {
+  return maybe_local_to_ptr(self.Call(context, recv, argc, argv));
+}
+
+v8::Object* v8__Function__NewInstance(const v8::Function& self,
+                                      v8::Local<v8::Context> context,
+                                      int argc, v8::Local<v8::Value>* argv) {
+  return maybe_local_to_ptr(self.NewInstance(context, argc, argv));
+}
+
+v8::Value* v8__Function__GetName(const v8::Function& self) {
+  return local_to_ptr(self.GetName());
+}
+
+void v8__Function__SetName(v8::Function& self, v8::Local<v8::String> name) {
+  self.SetName(name);
+}
+
+int v8__Function__GetScriptLineNumber(const v8::Function& self) {
+  return self.GetScriptLineNumber();
+}
+
+int v8__Function__GetScriptColumnNumber(const v8::Function& self) {
+  return self.GetScriptColumnNumber();
+}
+
+int v8__Function__ScriptId(const v8::Function& self) {
+  return self.ScriptId();
+}
+
+void v8__Template__Set(v8::Template& self, v8::Local<v8::Name> key,
+                       v8::Local<v8::Data> value,
+                       v8::PropertyAttribute attr) {
+  self.Set(key, value, attr);
+}
+
+void v8__Template__SetAccessorProperty(v8::Template& self,
+                                       v8::Local<v8::Name> key,
+                                       v8::FunctionTemplate* getter,
+                                       v8::FunctionTemplate* setter,
+                                       v8::PropertyAttribute attr) {
+  self.SetAccessorProperty(key, ptr_to_local(getter), ptr_to_local(setter),
+                           attr);
+}
+
+v8::ObjectTemplate* v8__ObjectTemplate__New(v8::Isolate* isolate) {
+  return local_to_ptr(v8::ObjectTemplate::New(isolate));
+}
+
+v8::Object* v8__ObjectTemplate__NewInstance(v8::ObjectTemplate& self,
+                                            v8::Local<v8::Context> context) {
+  return maybe_local_to_ptr(self.NewInstance(context));
+}
+
+int v8__ObjectTemplate__InternalFieldCount(const v8::ObjectTemplate& self) {
+  return self.InternalFieldCount();
+}
+
+void v8__ObjectTemplate__SetInternalFieldCount(v8::ObjectTemplate& self,
+                                               int value) {
+  self.SetInternalFieldCount(value);
+}
+
+void v8__ObjectTemplate__SetAccessor(v8::ObjectTemplate& self,
+                                     v8::Local<v8::Name> key,
+                                     v8::AccessorNameGetterCallback getter,
+                                     v8::AccessorNameSetterCallback setter) {
+  self.SetAccessor(key, getter, setter);
+}
+
+void v8__ObjectTemplate__SetNamedPropertyHandler(
+    v8::ObjectTemplate& self, v8::GenericNamedPropertyGetterCallback getter,
+    v8::GenericNamedPropertySetterCallback setter,
+    v8::GenericNamedPropertyQueryCallback query,
+    v8::GenericNamedPropertyDeleterCallback deleter,
+    v8::GenericNamedPropertyEnumeratorCallback enumerator) {
+  self.SetHandler(v8::NamedPropertyHandlerConfiguration(
+      getter, setter, query, deleter, enumerator));
+}
+
+void v8__ObjectTemplate__SetIndexedPropertyHandler(
+    v8::ObjectTemplate& self, v8::IndexedPropertyGetterCallback getter,
+    v8::IndexedPropertySetterCallback setter,
+    v8::IndexedPropertyQueryCallback query,
+    v8::IndexedPropertyDeleterCallback deleter,
+    v8::IndexedPropertyEnumeratorCallback enumerator) {
+  self.SetHandler(v8::IndexedPropertyHandlerConfiguration(
+      getter, setter, query, deleter, enumerator));
 }
 
 v8::FunctionTemplate* v8__FunctionTemplate__New(
-    v8::Isolate* isolate, v8::FunctionCallback callback = nullptr) {
-  return local_to_ptr(v8::FunctionTemplate::New(isolate, callback));
+    v8::Isolate* isolate, v8::FunctionCallback callback = nullptr,
+    v8::Value* data = nullptr) {
+  return local_to_ptr(
+      v8::FunctionTemplate::New(isolate, callback, ptr_to_local(data)));
 }
 
 v8::Function* v8__FunctionTemplate__GetFunction(
     v8::Local<v8::FunctionTemplate> self, v8::Local<v8::Context> context) {
   return maybe_local_to_ptr(self->GetFunction(context));
 }
+
+void v8__FunctionTemplate__SetCallHandler(v8::FunctionTemplate& self,
+                                          v8::FunctionCallback callback,
+                                          v8::Value* data) {
+  self.SetCallHandler(callback, ptr_to_local(data));
+}
+
+void v8__FunctionTemplate__SetLength(v8::FunctionTemplate& self, int length) {
+  self.SetLength(length);
+}
+
+void v8__FunctionTemplate__SetClassName(v8::FunctionTemplate& self,
+                                        v8::Local<v8::String> name) {
+  self.SetClassName(name);
+}
+
+v8::ObjectTemplate* v8__FunctionTemplate__InstanceTemplate(
+    v8::FunctionTemplate& self) {
+  return local_to_ptr(self.InstanceTemplate());
+}
+
+v8::ObjectTemplate* v8__FunctionTemplate__PrototypeTemplate(
+    v8::FunctionTemplate& self) {
+  return local_to_ptr(self.PrototypeTemplate());
+}
+
+void v8__FunctionTemplate__Inherit(v8::FunctionTemplate& self,
+                                   v8::Local<v8::FunctionTemplate> parent) {
+  self.Inherit(parent);
+}
+
+void v8__FunctionTemplate__ReadOnlyPrototype(v8::FunctionTemplate& self) {
+  self.ReadOnlyPrototype();
+}
+
+void v8__FunctionTemplate__RemovePrototype(v8::FunctionTemplate& self) {
+  self.RemovePrototype();
+}
+
+bool v8__FunctionTemplate__HasInstance(v8::FunctionTemplate& self,
+                                       v8::Local<v8::Value> object) {
+  return self.HasInstance(object);
+}
+
 int v8__FunctionCallbackInfo__Length(
     v8::FunctionCallbackInfo<v8::Value>* self) {
   return self->Length();
@@ -311,11 +1239,73 @@ void v8__FunctionCallbackInfo__GetReturnValue(
   *out = self->GetReturnValue();
 }
 
+v8::Value* v8__FunctionCallbackInfo__GetArgument(
+    v8::FunctionCallbackInfo<v8::Value>* self, int i) {
+  return local_to_ptr((*self)[i]);
+}
+
+v8::Object* v8__FunctionCallbackInfo__This(
+    v8::FunctionCallbackInfo<v8::Value>* self) {
+  return local_to_ptr(self->This());
+}
+
+v8::Object* v8__FunctionCallbackInfo__Holder(
+    v8::FunctionCallbackInfo<v8::Value>* self) {
+  return local_to_ptr(self->Holder());
+}
+
+v8::Value* v8__FunctionCallbackInfo__NewTarget(
+    v8::FunctionCallbackInfo<v8::Value>* self) {
+  return local_to_ptr(self->NewTarget());
+}
+
+bool v8__FunctionCallbackInfo__IsConstructCall(
+    v8::FunctionCallbackInfo<v8::Value>* self) {
+  return self->IsConstructCall();
+}
+
+v8::Value* v8__FunctionCallbackInfo__Data(
+    v8::FunctionCallbackInfo<v8::Value>* self) {
+  return local_to_ptr(self->Data());
+}
+
 void v8__ReturnValue__Set(v8::ReturnValue<v8::Value>* self,
                           v8::Local<v8::Value> value) {
   self->Set(value);
 }
 
+void v8__ReturnValue__Set__int32(v8::ReturnValue<v8::Value>* self,
+                                 int32_t value) {
+  self->Set(value);
+}
+
+void v8__ReturnValue__Set__uint32(v8::ReturnValue<v8::Value>* self,
+                                  uint32_t value) {
+  self->Set(value);
+}
+
+void v8__ReturnValue__Set__double(v8::ReturnValue<v8::Value>* self,
+                                  double value) {
+  self->Set(value);
+}
+
+void v8__ReturnValue__Set__bool(v8::ReturnValue<v8::Value>* self,
+                                bool value) {
+  self->Set(value);
+}
+
+void v8__ReturnValue__SetNull(v8::ReturnValue<v8::Value>* self) {
+  self->SetNull();
+}
+
+void v8__ReturnValue__SetUndefined(v8::ReturnValue<v8::Value>* self) {
+  self->SetUndefined();
+}
+
+void v8__ReturnValue__SetEmptyString(v8::ReturnValue<v8::Value>* self) {
+  self->SetEmptyString();
+}
+
 v8::Value* v8__ReturnValue__Get(v8::ReturnValue<v8::Value>* self) {
   return local_to_ptr(self->Get());
 }
@@ -477,6 +1467,9 @@ v8::Value* v8__PromiseRejectMessage__GetValue(
   return local_to_ptr(self.GetValue());
 }
 
+// The PropertyCallbackInfo functions below are also used for callbacks that
+// receive a PropertyCallbackInfo<Integer>, <Boolean> or <Array>. Their layout
+// does not depend on the type parameter.
 v8::Isolate* v8__PropertyCallbackInfo__GetIsolate(
     const v8::PropertyCallbackInfo<v8::Value>* self) {
   return self->GetIsolate();
@@ -487,6 +1480,16 @@ v8::Object* v8__PropertyCallbackInfo__This(
   return local_to_ptr(self->This());
 }
 
+v8::Object* v8__PropertyCallbackInfo__Holder(
+    const v8::PropertyCallbackInfo<v8::Value>* self) {
+  return local_to_ptr(self->Holder());
+}
+
+v8::Value* v8__PropertyCallbackInfo__Data(
+    const v8::PropertyCallbackInfo<v8::Value>* self) {
+  return local_to_ptr(self->Data());
+}
+
 void v8__PropertyCallbackInfo__GetReturnValue(
     const v8::PropertyCallbackInfo<v8::Value>* self,
     v8::ReturnValue<v8::Value>* out) {
@@ -1,4 +1,7 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use std::ops::Deref;
+use std::ops::DerefMut;
+
 use crate::isolate::Isolate;
 use crate::support::Opaque;
 use crate::HandleScope;
@@ -24,6 +27,11 @@ impl Context {
     unsafe { Local::from_raw(v8__Context__New(scope.as_mut())).unwrap() }
   }
 
+  /// Returns the isolate associated with this context.
+  pub fn get_isolate(&mut self) -> &mut Isolate {
+    unsafe { &mut *v8__Context__GetIsolate(self) }
+  }
+
   /// Returns the global proxy object.
   ///
   /// Global proxy object is a thin wrapper whose prototype points to actual
@@ -42,15 +50,83 @@ impl Context {
   /// and run is compiled and run in this context.  If another context
   /// is already entered, this old context is saved so it can be
   /// restored when the new context is exited.
-  pub fn enter(&mut self) {
-    // TODO: enter/exit should be controlled by a scope.
+  fn enter(&mut self) {
     unsafe { v8__Context__Enter(self) };
   }
 
   /// Exit this context.  Exiting the current context restores the
   /// context that was in place when entering the current context.
-  pub fn exit(&mut self) {
-    // TODO: enter/exit should be controlled by a scope.
+  fn exit(&mut self) {
     unsafe { v8__Context__Exit(self) };
   }
 }
+
+/// A scope which enters a context on construction and exits it again when it
+/// is dropped, restoring the context that was entered before.
+///
+/// A ContextScope borrows the HandleScope it was created from, and can be
+/// used in its place to create handles. Functions like `Script::compile()`
+/// take a ContextScope as proof that a context has been entered.
+pub struct ContextScope<'a, 'sc> {
+  scope: &'a mut HandleScope<'sc>,
+  context: Local<'sc, Context>,
+}
+
+impl<'a, 'sc> ContextScope<'a, 'sc> {
+  /// Enter `context` for the lifetime of the returned scope.
+  pub fn new(
+    scope: &'a mut HandleScope<'sc>,
+    mut context: Local<'sc, Context>,
+  ) -> Self {
+    context.enter();
+    Self { scope, context }
+  }
+
+  /// Returns the context that was entered by this scope.
+  pub fn context(&self) -> Local<'sc, Context> {
+    self.context
+  }
+}
+
+impl<'a, 'sc> Drop for ContextScope<'a, 'sc> {
+  fn drop(&mut self) {
+    self.context.exit()
+  }
+}
+
+impl<'a, 'sc> Deref for ContextScope<'a, 'sc> {
+  type Target = HandleScope<'sc>;
+  fn deref(&self) -> &Self::Target {
+    self.scope
+  }
+}
+
+impl<'a, 'sc> DerefMut for ContextScope<'a, 'sc> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.scope
+  }
+}
+
+impl<'a, 'sc> AsRef<HandleScope<'sc>> for ContextScope<'a, 'sc> {
+  fn as_ref(&self) -> &HandleScope<'sc> {
+    self.scope
+  }
+}
+
+impl<'a, 'sc> AsMut<HandleScope<'sc>> for ContextScope<'a, 'sc> {
+  fn as_mut(&mut self) -> &mut HandleScope<'sc> {
+    self.scope
+  }
+}
+
+impl<'a, 'sc> AsRef<Isolate> for ContextScope<'a, 'sc> {
+  fn as_ref(&self) -> &Isolate {
+    self.scope.as_ref()
+  }
+}
+
+impl<'a, 'sc> AsMut<Isolate> for ContextScope<'a, 'sc> {
+  fn as_mut(&mut self) -> &mut Isolate {
+    self.scope.as_mut()
+  }
+}
@@ -0,0 +1,44 @@
+use std::ffi::c_void;
+use std::ops::Deref;
+
+use crate::isolate::Isolate;
+use crate::support::Opaque;
+use crate::HandleScope;
+use crate::Local;
+use crate::Value;
+
+extern "C" {
+  fn v8__External__New(
+    isolate: *mut Isolate,
+    value: *mut c_void,
+  ) -> *mut External;
+  fn v8__External__Value(this: &External) -> *mut c_void;
+}
+
+/// A JavaScript value that wraps a C++ void*. This type of value is mainly
+/// used to associate C++ data structures with JavaScript objects.
+#[repr(C)]
+pub struct External(Opaque);
+
+impl External {
+  // The pointer is only stored, never dereferenced, by V8.
+  #[allow(clippy::not_unsafe_ptr_arg_deref)]
+  pub fn new<'sc>(
+    scope: &mut HandleScope<'sc>,
+    value: *mut c_void,
+  ) -> Local<'sc, External> {
+    unsafe { Local::from_raw(v8__External__New(scope.as_mut(), value)) }
+      .unwrap()
+  }
+
+  pub fn value(&self) -> *mut c_void {
+    unsafe { v8__External__Value(self) }
+  }
+}
+
+impl Deref for External {
+  type Target = Value;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Value) }
+  }
+}
@@ -1,32 +1,85 @@
 use crate::support::{int, Opaque};
 use crate::Context;
+use crate::External;
 use crate::HandleScope;
 use crate::Isolate;
 use crate::Local;
+use crate::Object;
+use crate::ObjectTemplate;
+use crate::String;
+use crate::Template;
 use crate::Value;
+use crate::Weak;
+use std::convert::TryFrom;
+use std::convert::TryInto;
+use std::ffi::c_void;
+use std::marker::PhantomData;
 use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::process;
 
 extern "C" {
   fn v8__Function__New(
     context: *mut Context,
     callback: extern "C" fn(&FunctionCallbackInfo),
+    data: *mut Value,
   ) -> *mut Function;
   fn v8__Function__Call(
-    function: *mut Function,
+    this: &Function,
     context: *mut Context,
     recv: *mut Value,
     argc: int,
-    argv: *mut *mut Value,
+    argv: *const Local<Value>,
   ) -> *mut Value;
+  fn v8__Function__NewInstance(
+    this: &Function,
+    context: *mut Context,
+    argc: int,
+    argv: *const Local<Value>,
+  ) -> *mut Object;
+  fn v8__Function__GetName(this: &Function) -> *mut Value;
+  fn v8__Function__SetName(this: &Function, name: *mut String);
+  fn v8__Function__GetScriptLineNumber(this: &Function) -> int;
+  fn v8__Function__GetScriptColumnNumber(this: &Function) -> int;
+  fn v8__Function__ScriptId(this: &Function) -> int;
 
   fn v8__FunctionTemplate__New(
     isolate: &Isolate,
     callback: extern "C" fn(&FunctionCallbackInfo),
+    data: *mut Value,
   ) -> *mut FunctionTemplate;
   fn v8__FunctionTemplate__GetFunction(
-    fn_template: *mut FunctionTemplate,
+    this: &FunctionTemplate,
     context: *mut Context,
   ) -> *mut Function;
+  fn v8__FunctionTemplate__SetCallHandler(
+    this: &FunctionTemplate,
+    callback: extern "C" fn(&FunctionCallbackInfo),
+    data: *mut Value,
+  );
+  fn v8__FunctionTemplate__SetLength(this: &FunctionTemplate, length: int);
+  fn v8__FunctionTemplate__SetClassName(
+    this: &FunctionTemplate,
+    name: *mut String,
+  );
+  fn v8__FunctionTemplate__InstanceTemplate(
+    this: &FunctionTemplate,
+  ) -> *mut ObjectTemplate;
+  fn v8__FunctionTemplate__PrototypeTemplate(
+    this: &FunctionTemplate,
+  ) -> *mut ObjectTemplate;
+  fn v8__FunctionTemplate__Inherit(
+    this: &FunctionTemplate,
+    parent: *mut FunctionTemplate,
+  );
+  fn v8__FunctionTemplate__ReadOnlyPrototype(this: &FunctionTemplate);
+  fn v8__FunctionTemplate__RemovePrototype(this: &FunctionTemplate);
+  fn v8__FunctionTemplate__HasInstance(
+    this: &FunctionTemplate,
+    object: *mut Value,
+  ) -> bool;
 
   fn v8__FunctionCallbackInfo__GetIsolate(
     info: &FunctionCallbackInfo,
@@ -36,29 +89,58 @@ extern "C" {
     info: &FunctionCallbackInfo,
     out: *mut ReturnValue,
   );
+  fn v8__FunctionCallbackInfo__GetArgument(
+    info: &FunctionCallbackInfo,
+    i: int,
+  ) -> *mut Value;
+  fn v8__FunctionCallbackInfo__This(info: &FunctionCallbackInfo)
+    -> *mut Object;
+  fn v8__FunctionCallbackInfo__Holder(
+    info: &FunctionCallbackInfo,
+  ) -> *mut Object;
+  fn v8__FunctionCallbackInfo__NewTarget(
+    info: &FunctionCallbackInfo,
+  ) -> *mut Value;
+  fn v8__FunctionCallbackInfo__IsConstructCall(
+    info: &FunctionCallbackInfo,
+  ) -> bool;
+  fn v8__FunctionCallbackInfo__Data(info: &FunctionCallbackInfo) -> *mut Value;
 
   fn v8__ReturnValue__Set(rv: *mut ReturnValue, value: *mut Value) -> ();
+  fn v8__ReturnValue__Set__int32(rv: *mut ReturnValue, value: i32);
+  fn v8__ReturnValue__Set__uint32(rv: *mut ReturnValue, value: u32);
+  fn v8__ReturnValue__Set__double(rv: *mut ReturnValue, value: f64);
+  fn v8__ReturnValue__Set__bool(rv: *mut ReturnValue, value: bool);
+  fn v8__ReturnValue__SetNull(rv: *mut ReturnValue);
+  fn v8__ReturnValue__SetUndefined(rv: *mut ReturnValue);
+  fn v8__ReturnValue__SetEmptyString(rv: *mut ReturnValue);
   fn v8__ReturnValue__Get(rv: *mut ReturnValue) -> *mut Value;
   fn v8__ReturnValue__GetIsolate(rv: &ReturnValue) -> *mut Isolate;
 }
 
+/// Like in V8, `T` is the type of the value that can be returned. Property
+/// callbacks that query, delete or enumerate properties must return an
+/// Integer, a Boolean or an Array respectively; all other callbacks return a
+/// Value.
 #[repr(C)]
-pub struct ReturnValue([usize; 1]);
+pub struct ReturnValue<T = Value>([usize; 1], PhantomData<T>);
 
-/// In V8 ReturnValue<> has a type parameter, but
-/// it turns out that in most of the APIs it's ReturnValue<Value>
-/// and for our purposes we currently don't need
-/// other types. So for now it's a simplified version.
-impl ReturnValue {
+impl<T> ReturnValue<T> {
   // NOTE: simplest setter, possibly we'll need to add
   // more setters specialized per type
-  pub fn set(&mut self, mut value: Local<Value>) {
-    unsafe { v8__ReturnValue__Set(&mut *self, &mut *value) }
+  pub fn set(&mut self, mut value: Local<T>) {
+    unsafe {
+      v8__ReturnValue__Set(
+        self.as_value_rv(),
+        &mut *value as *mut T as *mut Value,
+      )
+    }
   }
 
   /// Convenience getter for Isolate
   pub fn get_isolate(&self) -> &Isolate {
-    unsafe { v8__ReturnValue__GetIsolate(self).as_ref().unwrap() }
+    let rv = self as *const Self as *const ReturnValue;
+    unsafe { v8__ReturnValue__GetIsolate(&*rv).as_ref().unwrap() }
   }
 
   /// Getter. Creates a new Local<> so it comes with a certain performance
@@ -68,7 +150,55 @@ impl ReturnValue {
     &mut self,
     _scope: &mut HandleScope<'sc>,
   ) -> Local<'sc, Value> {
-    unsafe { Local::from_raw(v8__ReturnValue__Get(&mut *self)).unwrap() }
+    unsafe {
+      Local::from_raw(v8__ReturnValue__Get(self.as_value_rv())).unwrap()
+    }
+  }
+
+  // The layout of v8::ReturnValue<T> does not depend on T, so the bindings
+  // are only instantiated for ReturnValue<Value>.
+  fn as_value_rv(&mut self) -> *mut ReturnValue {
+    self as *mut Self as *mut ReturnValue
+  }
+}
+
+impl ReturnValue {
+  // The typed setters below use fast paths in V8 that avoid allocating a
+  // handle for the returned value.
+
+  /// Sets the return value to a 32-bit signed integer.
+  pub fn set_int32(&mut self, value: i32) {
+    unsafe { v8__ReturnValue__Set__int32(&mut *self, value) }
+  }
+
+  /// Sets the return value to a 32-bit unsigned integer.
+  pub fn set_uint32(&mut self, value: u32) {
+    unsafe { v8__ReturnValue__Set__uint32(&mut *self, value) }
+  }
+
+  /// Sets the return value to a double precision number.
+  pub fn set_double(&mut self, value: f64) {
+    unsafe { v8__ReturnValue__Set__double(&mut *self, value) }
+  }
+
+  /// Sets the return value to a boolean.
+  pub fn set_bool(&mut self, value: bool) {
+    unsafe { v8__ReturnValue__Set__bool(&mut *self, value) }
+  }
+
+  /// Sets the return value to null.
+  pub fn set_null(&mut self) {
+    unsafe { v8__ReturnValue__SetNull(&mut *self) }
+  }
+
+  /// Sets the return value to undefined.
+  pub fn set_undefined(&mut self) {
+    unsafe { v8__ReturnValue__SetUndefined(&mut *self) }
+  }
+
+  /// Sets the return value to the empty string.
+  pub fn set_empty_string(&mut self) {
+    unsafe { v8__ReturnValue__SetEmptyString(&mut *self) }
   }
 }
 
@@ -99,6 +229,131 @@ impl FunctionCallbackInfo {
   pub fn length(&self) -> int {
     unsafe { v8__FunctionCallbackInfo__Length(&*self) }
   }
+
+  /// Accessor for the available arguments. Returns the undefined value if
+  /// `i` is out of bounds.
+  pub fn get_argument(&self, i: int) -> Local<'_, Value> {
+    unsafe {
+      Local::from_raw(v8__FunctionCallbackInfo__GetArgument(self, i)).unwrap()
+    }
+  }
+
+  /// Returns the receiver. This corresponds to the "this" value.
+  pub fn this(&self) -> Local<'_, Object> {
+    unsafe { Local::from_raw(v8__FunctionCallbackInfo__This(self)).unwrap() }
+  }
+
+  /// If the callback was created without a Signature, this is the same
+  /// value as This(). If there is a signature, and the signature didn't match
+  /// This() but one of its hidden prototypes, this will be the respective
+  /// hidden prototype.
+  ///
+  /// Note that this is not the prototype of This() on which the accessor
+  /// referencing this callback was found (which in V8 internally is often
+  /// referred to as holder [sic]).
+  pub fn holder(&self) -> Local<'_, Object> {
+    unsafe { Local::from_raw(v8__FunctionCallbackInfo__Holder(self)).unwrap() }
+  }
+
+  /// For construct calls, this returns the "new.target" value.
+  pub fn new_target(&self) -> Local<'_, Value> {
+    unsafe {
+      Local::from_raw(v8__FunctionCallbackInfo__NewTarget(self)).unwrap()
+    }
+  }
+
+  /// Indicates whether this is a regular call or a construct call.
+  pub fn is_construct_call(&self) -> bool {
+    unsafe { v8__FunctionCallbackInfo__IsConstructCall(self) }
+  }
+
+  /// The data argument specified when creating the callback.
+  pub fn data(&self) -> Local<'_, Value> {
+    unsafe { Local::from_raw(v8__FunctionCallbackInfo__Data(self)).unwrap() }
+  }
+}
+
+/// The arguments passed to a function implemented by a Rust closure. This
+/// is the same as FunctionCallbackInfo, except that the data slot is not
+/// exposed because it is used to hold the closure itself.
+#[derive(Clone, Copy)]
+pub struct FunctionCallbackArguments<'a> {
+  info: &'a FunctionCallbackInfo,
+}
+
+impl<'a> FunctionCallbackArguments<'a> {
+  /// The current Isolate.
+  pub fn get_isolate(&self) -> &Isolate {
+    self.info.get_isolate()
+  }
+
+  /// The number of available arguments.
+  pub fn length(&self) -> int {
+    self.info.length()
+  }
+
+  /// Accessor for the available arguments. Returns the undefined value if
+  /// `i` is out of bounds.
+  pub fn get(&self, i: int) -> Local<'a, Value> {
+    self.info.get_argument(i)
+  }
+
+  /// Returns the receiver. This corresponds to the "this" value.
+  pub fn this(&self) -> Local<'a, Object> {
+    self.info.this()
+  }
+
+  /// See FunctionCallbackInfo::holder().
+  pub fn holder(&self) -> Local<'a, Object> {
+    self.info.holder()
+  }
+
+  /// For construct calls, this returns the "new.target" value.
+  pub fn new_target(&self) -> Local<'a, Value> {
+    self.info.new_target()
+  }
+
+  /// Indicates whether this is a regular call or a construct call.
+  pub fn is_construct_call(&self) -> bool {
+    self.info.is_construct_call()
+  }
+}
+
+extern "C" fn closure_callback<F>(info: &FunctionCallbackInfo)
+where
+  F: Fn(&mut HandleScope, FunctionCallbackArguments, ReturnValue),
+{
+  // Unwinding into V8 is not allowed, so a panic aborts the process instead.
+  let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    let data: Local<External> = info.data().try_into().unwrap();
+    let closure = unsafe { &*(data.value() as *const F) };
+    HandleScope::enter(info.get_isolate(), |scope| {
+      let args = FunctionCallbackArguments { info };
+      closure(scope, args, info.get_return_value())
+    });
+  }));
+  if result.is_err() {
+    process::abort()
+  }
+}
+
+// Moves `closure` to the heap and wraps it in an External, which is passed as
+// the data argument of the callback. The closure is dropped when the External
+// is garbage collected, or when the isolate is disposed.
+fn closure_data<'sc, F>(
+  scope: &mut HandleScope<'sc>,
+  closure: F,
+) -> (extern "C" fn(&FunctionCallbackInfo), Local<'sc, Value>)
+where
+  F: Fn(&mut HandleScope, FunctionCallbackArguments, ReturnValue) + 'static,
+{
+  let ptr = Box::into_raw(Box::new(closure));
+  let external = External::new(scope, ptr as *mut c_void);
+  // Dropping the Weak does not cancel its finalizer.
+  Weak::with_finalizer(scope, external, move |_| unsafe {
+    drop(Box::from_raw(ptr))
+  });
+  (closure_callback::<F>, external.into())
 }
 
 /// A FunctionTemplate is used to create functions at runtime. There
@@ -131,24 +386,129 @@ impl FunctionTemplate {
     callback: extern "C" fn(&FunctionCallbackInfo),
   ) -> Local<'sc, FunctionTemplate> {
     unsafe {
-      Local::from_raw(v8__FunctionTemplate__New(scope.as_mut(), callback))
-        .unwrap()
+      Local::from_raw(v8__FunctionTemplate__New(
+        scope.as_mut(),
+        callback,
+        std::ptr::null_mut(),
+      ))
+      .unwrap()
+    }
+  }
+
+  /// Creates a function template whose call handler is a Rust closure. The
+  /// closure is freed once the template and all functions created from it
+  /// have been garbage collected, or when the isolate is disposed. A panic in
+  /// the closure aborts the process.
+  pub fn new_closure<'sc>(
+    scope: &mut HandleScope<'sc>,
+    closure: impl Fn(&mut HandleScope, FunctionCallbackArguments, ReturnValue)
+      + 'static,
+  ) -> Local<'sc, FunctionTemplate> {
+    let (callback, mut data) = closure_data(scope, closure);
+    unsafe {
+      Local::from_raw(v8__FunctionTemplate__New(
+        scope.as_mut(),
+        callback,
+        &mut *data,
+      ))
+      .unwrap()
     }
   }
 
   /// Returns the unique function instance in the current execution context.
   pub fn get_function<'sc>(
-    &mut self,
+    &self,
     _scope: &mut HandleScope<'sc>,
     mut context: Local<Context>,
   ) -> Option<Local<'sc, Function>> {
     unsafe {
-      Local::from_raw(v8__FunctionTemplate__GetFunction(
-        &mut *self,
-        &mut *context,
-      ))
+      Local::from_raw(v8__FunctionTemplate__GetFunction(self, &mut *context))
+    }
+  }
+
+  /// Sets the callback that is invoked when a function created from this
+  /// template is called.
+  pub fn set_call_handler(
+    &self,
+    callback: extern "C" fn(&FunctionCallbackInfo),
+  ) {
+    unsafe {
+      v8__FunctionTemplate__SetCallHandler(self, callback, std::ptr::null_mut())
     }
   }
+
+  /// Like `set_call_handler()`, but `data` is made available to the callback
+  /// through `FunctionCallbackInfo::data()`.
+  pub fn set_call_handler_with_data(
+    &self,
+    callback: extern "C" fn(&FunctionCallbackInfo),
+    mut data: Local<Value>,
+  ) {
+    unsafe { v8__FunctionTemplate__SetCallHandler(self, callback, &mut *data) }
+  }
+
+  /// Set the predefined length property for the FunctionTemplate.
+  pub fn set_length(&self, length: int) {
+    unsafe { v8__FunctionTemplate__SetLength(self, length) }
+  }
+
+  /// Set the class name of the FunctionTemplate. This is used for
+  /// printing objects created with the function created from the
+  /// FunctionTemplate as its constructor.
+  pub fn set_class_name(&self, mut name: Local<String>) {
+    unsafe { v8__FunctionTemplate__SetClassName(self, &mut *name) }
+  }
+
+  /// Get the InstanceTemplate.
+  pub fn instance_template<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+  ) -> Local<'sc, ObjectTemplate> {
+    unsafe { Local::from_raw(v8__FunctionTemplate__InstanceTemplate(self)) }
+      .unwrap()
+  }
+
+  /// A PrototypeTemplate is the template used to create the prototype object
+  /// of the function created by this template.
+  pub fn prototype_template<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+  ) -> Local<'sc, ObjectTemplate> {
+    unsafe { Local::from_raw(v8__FunctionTemplate__PrototypeTemplate(self)) }
+      .unwrap()
+  }
+
+  /// Causes the function template to inherit from a parent function template.
+  /// This means the function's prototype.__proto__ is set to the parent
+  /// function's prototype.
+  pub fn inherit(&self, mut parent: Local<FunctionTemplate>) {
+    unsafe { v8__FunctionTemplate__Inherit(self, &mut *parent) }
+  }
+
+  /// Sets the ReadOnly flag in the attributes of the 'prototype' property
+  /// of functions created from this FunctionTemplate to true.
+  pub fn read_only_prototype(&self) {
+    unsafe { v8__FunctionTemplate__ReadOnlyPrototype(self) }
+  }
+
+  /// Removes the prototype property from functions created from this
+  /// FunctionTemplate.
+  pub fn remove_prototype(&self) {
+    unsafe { v8__FunctionTemplate__RemovePrototype(self) }
+  }
+
+  /// Returns true if the given object is an instance of this function
+  /// template.
+  pub fn has_instance(&self, mut object: Local<Value>) -> bool {
+    unsafe { v8__FunctionTemplate__HasInstance(self, &mut *object) }
+  }
+}
+
+impl Deref for FunctionTemplate {
+  type Target = Template;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Template) }
+  }
 }
 
 /// A JavaScript function object (ECMA-262, 15.3).
@@ -164,29 +524,108 @@ impl Function {
     mut context: Local<Context>,
     callback: extern "C" fn(&FunctionCallbackInfo),
   ) -> Option<Local<'sc, Function>> {
-    unsafe { Local::from_raw(v8__Function__New(&mut *context, callback)) }
+    unsafe {
+      Local::from_raw(v8__Function__New(
+        &mut *context,
+        callback,
+        std::ptr::null_mut(),
+      ))
+    }
   }
 
+  /// Create a function in the current execution context whose behavior is
+  /// implemented by a Rust closure. The closure is freed once the function
+  /// has been garbage collected, or when the isolate is disposed. A panic in
+  /// the closure aborts the process.
+  pub fn new_closure<'sc>(
+    scope: &mut HandleScope<'sc>,
+    mut context: Local<Context>,
+    closure: impl Fn(&mut HandleScope, FunctionCallbackArguments, ReturnValue)
+      + 'static,
+  ) -> Option<Local<'sc, Function>> {
+    let (callback, mut data) = closure_data(scope, closure);
+    unsafe {
+      Local::from_raw(v8__Function__New(&mut *context, callback, &mut *data))
+    }
+  }
+
+  /// Calls the function with `recv` as the receiver ("this" value), passing
+  /// `args` as the arguments.
   pub fn call<'sc>(
-    &mut self,
+    &self,
     _scope: &mut HandleScope<'sc>,
     mut context: Local<Context>,
     mut recv: Local<Value>,
-    arc: i32,
-    argv: Vec<Local<Value>>,
+    args: &[Local<Value>],
   ) -> Option<Local<'sc, Value>> {
-    let mut argv_: Vec<*mut Value> = vec![];
-    for mut arg in argv {
-      argv_.push(&mut *arg);
-    }
+    let argc = int::try_from(args.len()).unwrap();
     unsafe {
       Local::from_raw(v8__Function__Call(
-        &mut *self,
+        self,
         &mut *context,
         &mut *recv,
-        arc,
-        argv_.as_mut_ptr(),
+        argc,
+        args.as_ptr(),
+      ))
+    }
+  }
+
+  /// Calls the function as a constructor, like the JavaScript `new`
+  /// operator, passing `args` as the arguments.
+  pub fn new_instance<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<Context>,
+    args: &[Local<Value>],
+  ) -> Option<Local<'sc, Object>> {
+    let argc = int::try_from(args.len()).unwrap();
+    unsafe {
+      Local::from_raw(v8__Function__NewInstance(
+        self,
+        &mut *context,
+        argc,
+        args.as_ptr(),
       ))
     }
   }
+
+  /// Returns the name of the function, as given by the `name` property.
+  pub fn get_name<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+  ) -> Local<'sc, Value> {
+    unsafe { Local::from_raw(v8__Function__GetName(self)) }.unwrap()
+  }
+
+  /// Sets the name of the function, as reported by its `name` property.
+  pub fn set_name(&self, mut name: Local<String>) {
+    unsafe { v8__Function__SetName(self, &mut *name) }
+  }
+
+  /// Returns the zero based line number of the function body, or None if the
+  /// function was not defined in a script.
+  pub fn get_script_line_number(&self) -> Option<u32> {
+    let line = unsafe { v8__Function__GetScriptLineNumber(self) };
+    u32::try_from(line).ok()
+  }
+
+  /// Returns the zero based column number of the function body, or None if
+  /// the function was not defined in a script.
+  pub fn get_script_column_number(&self) -> Option<u32> {
+    let column = unsafe { v8__Function__GetScriptColumnNumber(self) };
+    u32::try_from(column).ok()
+  }
+
+  /// Returns the id of the script the function was defined in, or 0 if the
+  /// function was not defined in a script.
+  pub fn script_id(&self) -> int {
+    unsafe { v8__Function__ScriptId(self) }
+  }
+}
+
+impl Deref for Function {
+  type Target = Object;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Object) }
+  }
 }
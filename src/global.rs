@@ -0,0 +1,326 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+use crate::isolate::IsolateAnnex;
+use crate::HandleScope;
+use crate::Isolate;
+use crate::Local;
+use crate::Value;
+
+extern "C" {
+  fn v8__Global__New(isolate: *mut Isolate, other: *mut Value) -> *mut Value;
+  fn v8__Global__Get(this: *mut Value, isolate: *mut Isolate) -> *mut Value;
+  fn v8__Global__Reset(this: *mut Value);
+  fn v8__Global__SetWeak(this: *mut Value, parameter: *mut c_void);
+  fn v8__Global__ClearWeak(this: *mut Value) -> *mut c_void;
+}
+
+// The parameter is the address of a WeakData that is kept alive by the
+// isolate's annex until the weak callback has run.
+#[no_mangle]
+pub unsafe extern "C" fn v8__Weak__FirstPassCallback(parameter: *mut c_void) {
+  let data = &*(parameter as *const WeakData);
+  if let Some(cell) = data.cell.take() {
+    v8__Global__Reset(cell.as_ptr())
+  }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn v8__Weak__SecondPassCallback(
+  parameter: *mut c_void,
+  isolate: &mut Isolate,
+) {
+  let data = isolate
+    .annex()
+    .weak_data
+    .borrow_mut()
+    .remove(&(parameter as *const WeakData));
+  if let Some(data) = data {
+    data.finalize(isolate)
+  }
+}
+
+// Called right before an isolate is disposed. Objects that are still alive at
+// that point will never be collected, so the storage cells of all weak
+// handles are freed and their pending finalizers are run now.
+pub(crate) fn dispose_weak_handles(
+  isolate: &mut Isolate,
+  annex: &IsolateAnnex,
+) {
+  // Finalizers may create new weak handles, so repeat until none are left.
+  loop {
+    let pending = annex.weak_data.replace(Default::default());
+    if pending.is_empty() {
+      break;
+    }
+    for data in pending.into_values() {
+      if let Some(cell) = data.cell.take() {
+        unsafe { v8__Global__Reset(cell.as_ptr()) }
+      }
+      data.finalize(isolate)
+    }
+  }
+}
+
+/// An object reference that is independent of any handle scope. Where
+/// a Local handle only lives as long as the HandleScope in which it was
+/// allocated, a global handle remains valid until it is reset or dropped.
+///
+/// A global handle contains a reference to a storage cell within
+/// the V8 engine which holds an object value and which is updated by
+/// the garbage collector whenever the object is moved.
+///
+/// A Global remembers the isolate it was created in. Using it with another
+/// isolate panics. The storage cell is freed when the Global is reset or
+/// dropped, or together with the isolate if that is disposed first. Like
+/// Local handles, globals are neither `Send` nor `Sync`: they may only be
+/// used, reset and dropped by the thread that currently holds the isolate's
+/// Locker.
+///
+/// Note: in contrast to Local handles, a Global may be empty.
+pub struct Global<T> {
+  value: Option<NonNull<T>>,
+  annex: Option<Rc<IsolateAnnex>>,
+}
+
+impl<T> Global<T> {
+  /// Construct a new Global from an existing Local handle. A new storage cell
+  /// is created pointing to the same object.
+  pub fn new(isolate: &mut impl AsMut<Isolate>, other: Local<'_, T>) -> Self {
+    let isolate = isolate.as_mut();
+    let other = &*other as *const T as *mut Value;
+    let cell = unsafe { v8__Global__New(isolate, other) };
+    Self {
+      value: NonNull::new(cell as *mut T),
+      annex: Some(isolate.annex()),
+    }
+  }
+
+  /// Construct a Global with no storage cell.
+  pub fn empty() -> Self {
+    Self {
+      value: None,
+      annex: None,
+    }
+  }
+
+  /// Returns true if this Global is empty, i.e., has not been assigned an
+  /// object.
+  pub fn is_empty(&self) -> bool {
+    self.value.is_none()
+  }
+
+  /// Construct a Local<T> from this global handle.
+  ///
+  /// Panics if `scope` belongs to a different isolate than the one the
+  /// Global was created in.
+  pub fn get<'sc>(
+    &self,
+    scope: &mut HandleScope<'sc>,
+  ) -> Option<Local<'sc, T>> {
+    let cell = self.value?.as_ptr() as *mut Value;
+    let isolate: &mut Isolate = scope.as_mut();
+    self.annex.as_ref().unwrap().assert_isolate(isolate);
+    unsafe { Local::from_raw(v8__Global__Get(cell, isolate) as *mut T) }
+  }
+
+  /// If non-empty, destroy the underlying storage cell and create a new one
+  /// with the contents of `other`.
+  ///
+  /// Panics if the Global is non-empty and `isolate` is not the isolate it
+  /// was created in.
+  pub fn set(
+    &mut self,
+    isolate: &mut impl AsMut<Isolate>,
+    other: Local<'_, T>,
+  ) {
+    let isolate = isolate.as_mut();
+    if let (Some(_), Some(annex)) = (self.value, &self.annex) {
+      annex.assert_isolate(isolate);
+    }
+    *self = Self::new(isolate, other);
+  }
+
+  /// If non-empty, destroy the underlying storage cell. The Global is empty
+  /// afterwards.
+  pub fn reset(&mut self) {
+    let annex = self.annex.take();
+    if let (Some(cell), Some(annex)) = (self.value.take(), annex) {
+      // The cell has already been freed if the isolate was disposed.
+      if annex.is_alive() {
+        unsafe { v8__Global__Reset(cell.as_ptr() as *mut Value) }
+      }
+    }
+  }
+}
+
+impl<T> Default for Global<T> {
+  fn default() -> Self {
+    Self::empty()
+  }
+}
+
+impl<T> Drop for Global<T> {
+  fn drop(&mut self) {
+    self.reset()
+  }
+}
+
+type WeakFinalizer = dyn FnOnce(&mut Isolate);
+
+// State shared between a Weak handle and the weak callback registered with
+// V8. The isolate's annex holds a reference until the callback has run, so
+// that the finalizer can still run after the Weak itself has been dropped.
+pub(crate) struct WeakData {
+  cell: Cell<Option<NonNull<Value>>>,
+  finalizer: RefCell<Option<Box<WeakFinalizer>>>,
+}
+
+impl WeakData {
+  fn finalize(&self, isolate: &mut Isolate) {
+    let finalizer = self.finalizer.borrow_mut().take();
+    if let Some(finalizer) = finalizer {
+      finalizer(isolate)
+    }
+  }
+}
+
+/// A handle that does not keep the object it refers to alive. When the object
+/// is garbage collected, the handle becomes empty and the finalizer associated
+/// with it, if any, is invoked.
+///
+/// A finalizer is bound to the lifetime of the object, not to that of the
+/// handle: it still runs when the object is collected after the Weak has been
+/// dropped. This makes it suitable for freeing native resources that back a
+/// JavaScript wrapper object. Until then, the storage cell of the handle is
+/// kept as well. The finalizers of objects that are still alive when an
+/// OwnedIsolate is dropped run right before the isolate is disposed, so every
+/// finalizer runs exactly once.
+///
+/// Like Global, a Weak may only be used with the isolate it was created in,
+/// and only by the thread that currently holds the isolate's Locker.
+pub struct Weak<T> {
+  data: Option<Rc<WeakData>>,
+  annex: Option<Rc<IsolateAnnex>>,
+  _phantom: PhantomData<T>,
+}
+
+impl<T> Weak<T> {
+  /// Construct a new Weak handle that refers to the same object as `other`.
+  pub fn new(isolate: &mut impl AsMut<Isolate>, other: Local<'_, T>) -> Self {
+    Self::new_impl(isolate, other, None)
+  }
+
+  /// Construct a new Weak handle that refers to the same object as `other`.
+  /// The finalizer is invoked after the object has been garbage collected,
+  /// or when the isolate is disposed, whichever happens first.
+  pub fn with_finalizer(
+    isolate: &mut impl AsMut<Isolate>,
+    other: Local<'_, T>,
+    finalizer: impl FnOnce(&mut Isolate) + 'static,
+  ) -> Self {
+    Self::new_impl(isolate, other, Some(Box::new(finalizer)))
+  }
+
+  fn new_impl(
+    isolate: &mut impl AsMut<Isolate>,
+    other: Local<'_, T>,
+    finalizer: Option<Box<WeakFinalizer>>,
+  ) -> Self {
+    let isolate = isolate.as_mut();
+    let annex = isolate.annex();
+    let other = &*other as *const T as *mut Value;
+    let cell = unsafe { v8__Global__New(isolate, other) };
+    let data = Rc::new(WeakData {
+      cell: Cell::new(NonNull::new(cell)),
+      finalizer: RefCell::new(finalizer),
+    });
+    let parameter = Rc::as_ptr(&data);
+    annex.weak_data.borrow_mut().insert(parameter, data.clone());
+    unsafe { v8__Global__SetWeak(cell, parameter as *mut c_void) };
+    Self {
+      data: Some(data),
+      annex: Some(annex),
+      _phantom: PhantomData,
+    }
+  }
+
+  /// Construct a Weak with no storage cell.
+  pub fn empty() -> Self {
+    Self {
+      data: None,
+      annex: None,
+      _phantom: PhantomData,
+    }
+  }
+
+  /// Returns true if this Weak is empty, either because it has not been
+  /// assigned an object or because the object has been garbage collected.
+  pub fn is_empty(&self) -> bool {
+    self.cell().is_none()
+  }
+
+  /// Construct a Local<T> from this weak handle, if the object it refers to
+  /// is still alive.
+  ///
+  /// Panics if `scope` belongs to a different isolate than the one the Weak
+  /// was created in.
+  pub fn get<'sc>(
+    &self,
+    scope: &mut HandleScope<'sc>,
+  ) -> Option<Local<'sc, T>> {
+    let cell = self.cell()?.as_ptr();
+    let isolate: &mut Isolate = scope.as_mut();
+    self.annex.as_ref().unwrap().assert_isolate(isolate);
+    unsafe { Local::from_raw(v8__Global__Get(cell, isolate) as *mut T) }
+  }
+
+  /// Empties this handle. If the object it refers to has a finalizer that has
+  /// not run yet, the underlying storage cell is kept until the object is
+  /// garbage collected or the isolate is disposed, otherwise it is destroyed
+  /// immediately.
+  pub fn reset(&mut self) {
+    let annex = self.annex.take();
+    let data = match self.data.take() {
+      Some(data) => data,
+      None => return,
+    };
+    // The cell is only set while the isolate is alive.
+    let cell = match data.cell.get() {
+      Some(cell) => cell,
+      None => return,
+    };
+    if data.finalizer.borrow().is_none() {
+      data.cell.set(None);
+      annex
+        .unwrap()
+        .weak_data
+        .borrow_mut()
+        .remove(&Rc::as_ptr(&data));
+      unsafe {
+        v8__Global__ClearWeak(cell.as_ptr());
+        v8__Global__Reset(cell.as_ptr());
+      }
+    }
+  }
+
+  fn cell(&self) -> Option<NonNull<Value>> {
+    self.data.as_ref()?.cell.get()
+  }
+}
+
+impl<T> Default for Weak<T> {
+  fn default() -> Self {
+    Self::empty()
+  }
+}
+
+impl<T> Drop for Weak<T> {
+  fn drop(&mut self) {
+    self.reset()
+  }
+}
@@ -1,7 +1,11 @@
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::ops::DerefMut;
 
 use crate::isolate::Isolate;
+use crate::Local;
+use crate::Value;
 
 extern "C" {
   fn v8__HandleScope__CONSTRUCT(
@@ -12,6 +16,16 @@ extern "C" {
   fn v8__HandleScope__GetIsolate<'sc>(
     this: &'sc HandleScope,
   ) -> &'sc mut Isolate;
+
+  fn v8__EscapableHandleScope__CONSTRUCT(
+    buf: &mut MaybeUninit<CxxEscapableHandleScope>,
+    isolate: &Isolate,
+  );
+  fn v8__EscapableHandleScope__DESTRUCT(this: &mut CxxEscapableHandleScope);
+  fn v8__EscapableHandleScope__Escape(
+    this: &mut CxxEscapableHandleScope,
+    value: *mut Value,
+  ) -> *mut Value;
 }
 
 #[repr(C)]
@@ -54,3 +68,94 @@ impl<'sc> AsMut<Isolate> for HandleScope<'sc> {
     unsafe { v8__HandleScope__GetIsolate(self) }
   }
 }
+
+#[repr(C)]
+struct CxxEscapableHandleScope([usize; 4]);
+
+/// A HandleScope which first allocates a handle in the current scope
+/// which will be later filled with the escape value.
+///
+/// Local handles created in an EscapableHandleScope can't outlive it, except
+/// for the single handle that is passed to `escape()`, which is promoted to
+/// the lifetime of the parent scope.
+#[repr(C)]
+pub struct EscapableHandleScope<'p, 'sc> {
+  cxx_scope: CxxEscapableHandleScope,
+  escaped: bool,
+  _phantom: PhantomData<(&'p mut (), &'sc mut ())>,
+}
+
+impl<'p> EscapableHandleScope<'p, '_> {
+  pub fn enter<R>(
+    parent: &mut HandleScope<'p>,
+    f: impl FnOnce(&mut EscapableHandleScope<'p, '_>) -> R,
+  ) -> R {
+    let mut scope: MaybeUninit<Self> = MaybeUninit::uninit();
+    let scope_ptr = scope.as_mut_ptr();
+    unsafe {
+      v8__EscapableHandleScope__CONSTRUCT(
+        &mut *(scope_ptr as *mut MaybeUninit<CxxEscapableHandleScope>),
+        parent.as_ref(),
+      );
+      (*scope_ptr).escaped = false;
+    };
+    let scope = unsafe { &mut *scope_ptr };
+    let result = f(scope);
+
+    unsafe { v8__EscapableHandleScope__DESTRUCT(&mut scope.cxx_scope) };
+    result
+  }
+}
+
+impl<'p, 'sc> EscapableHandleScope<'p, 'sc> {
+  /// Pushes the value into the previous scope and returns a handle to it.
+  /// Cannot be called twice.
+  pub fn escape<T>(&mut self, mut value: Local<'sc, T>) -> Local<'p, T> {
+    assert!(!self.escaped, "EscapableHandleScope::escape() called twice");
+    self.escaped = true;
+    unsafe {
+      Local::from_raw(v8__EscapableHandleScope__Escape(
+        &mut self.cxx_scope,
+        &mut *value as *mut T as *mut Value,
+      ) as *mut T)
+    }
+    .unwrap()
+  }
+}
+
+impl<'p, 'sc> Deref for EscapableHandleScope<'p, 'sc> {
+  type Target = HandleScope<'sc>;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const HandleScope<'sc>) }
+  }
+}
+
+impl<'p, 'sc> DerefMut for EscapableHandleScope<'p, 'sc> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    unsafe { &mut *(self as *mut _ as *mut HandleScope<'sc>) }
+  }
+}
+
+impl<'p, 'sc> AsRef<HandleScope<'sc>> for EscapableHandleScope<'p, 'sc> {
+  fn as_ref(&self) -> &HandleScope<'sc> {
+    self
+  }
+}
+
+impl<'p, 'sc> AsMut<HandleScope<'sc>> for EscapableHandleScope<'p, 'sc> {
+  fn as_mut(&mut self) -> &mut HandleScope<'sc> {
+    self
+  }
+}
+
+impl<'p, 'sc> AsRef<Isolate> for EscapableHandleScope<'p, 'sc> {
+  fn as_ref(&self) -> &Isolate {
+    self.deref().as_ref()
+  }
+}
+
+impl<'p, 'sc> AsMut<Isolate> for EscapableHandleScope<'p, 'sc> {
+  fn as_mut(&mut self) -> &mut Isolate {
+    self.deref_mut().as_mut()
+  }
+}
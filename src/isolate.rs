@@ -1,25 +1,56 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 use crate::array_buffer::Allocator;
+use crate::global::dispose_weak_handles;
+use crate::global::WeakData;
 use crate::promise::PromiseRejectMessage;
+use crate::support::char;
+use crate::support::int;
 use crate::support::Delete;
 use crate::support::Opaque;
 use crate::support::UniqueRef;
 use crate::Local;
 use crate::Message;
 use crate::Value;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::mem::ManuallyDrop;
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::ptr::NonNull;
+use std::rc::Rc;
 
 type MessageCallback = extern "C" fn(Local<Message>, Local<Value>);
 
 type PromiseRejectCallback = extern "C" fn(PromiseRejectMessage);
 
+/// Returns the location of the counter with the given name, or null if V8
+/// should not keep track of it.
+pub type CounterLookupCallback = extern "C" fn(name: *const char) -> *mut int;
+
+/// Creates a histogram with the given name and bucket layout, and returns an
+/// opaque pointer to it that is passed to AddHistogramSampleCallback.
+pub type CreateHistogramCallback = extern "C" fn(
+  name: *const char,
+  min: int,
+  max: int,
+  buckets: usize,
+) -> *mut c_void;
+
+/// Adds a sample to a histogram created by CreateHistogramCallback.
+pub type AddHistogramSampleCallback =
+  extern "C" fn(histogram: *mut c_void, sample: int);
+
 extern "C" {
   fn v8__Isolate__New(params: *mut CreateParams) -> *mut Isolate;
   fn v8__Isolate__Dispose(this: *mut Isolate);
   fn v8__Isolate__Enter(this: *mut Isolate);
   fn v8__Isolate__Exit(this: *mut Isolate);
+  fn v8__Isolate__GetCurrent() -> *mut Isolate;
+  fn v8__Isolate__SetData(this: *mut Isolate, slot: u32, data: *mut c_void);
+  fn v8__Isolate__GetData(this: *const Isolate, slot: u32) -> *mut c_void;
+  fn v8__Isolate__IsInUse(this: *const Isolate) -> bool;
   fn v8__Isolate__SetCaptureStackTraceForUncaughtExceptions(
     this: *mut Isolate,
     caputre: bool,
@@ -40,7 +71,72 @@ extern "C" {
     this: &mut CreateParams,
     value: *mut Allocator,
   );
+  fn v8__Isolate__CreateParams__CONSTRAINTS(
+    this: &mut CreateParams,
+  ) -> *mut ResourceConstraints;
+  fn v8__Isolate__CreateParams__SET__external_references(
+    this: &mut CreateParams,
+    value: *const isize,
+  );
+  fn v8__Isolate__CreateParams__SET__counter_lookup_callback(
+    this: &mut CreateParams,
+    value: CounterLookupCallback,
+  );
+  fn v8__Isolate__CreateParams__SET__create_histogram_callback(
+    this: &mut CreateParams,
+    value: CreateHistogramCallback,
+  );
+  fn v8__Isolate__CreateParams__SET__add_histogram_sample_callback(
+    this: &mut CreateParams,
+    value: AddHistogramSampleCallback,
+  );
+  fn v8__Isolate__CreateParams__SET__allow_atomics_wait(
+    this: &mut CreateParams,
+    value: bool,
+  );
+  fn v8__Isolate__CreateParams__SET__only_terminate_in_safe_scope(
+    this: &mut CreateParams,
+    value: bool,
+  );
 
+  fn v8__ResourceConstraints__ConfigureDefaultsFromHeapSize(
+    this: &mut ResourceConstraints,
+    initial_heap_size_in_bytes: usize,
+    maximum_heap_size_in_bytes: usize,
+  );
+  fn v8__ResourceConstraints__ConfigureDefaults(
+    this: &mut ResourceConstraints,
+    physical_memory: u64,
+    virtual_memory_limit: u64,
+  );
+  fn v8__ResourceConstraints__max_old_generation_size_in_bytes(
+    this: &ResourceConstraints,
+  ) -> usize;
+  fn v8__ResourceConstraints__set_max_old_generation_size_in_bytes(
+    this: &mut ResourceConstraints,
+    limit: usize,
+  );
+  fn v8__ResourceConstraints__max_young_generation_size_in_bytes(
+    this: &ResourceConstraints,
+  ) -> usize;
+  fn v8__ResourceConstraints__set_max_young_generation_size_in_bytes(
+    this: &mut ResourceConstraints,
+    limit: usize,
+  );
+  fn v8__ResourceConstraints__initial_old_generation_size_in_bytes(
+    this: &ResourceConstraints,
+  ) -> usize;
+  fn v8__ResourceConstraints__set_initial_old_generation_size_in_bytes(
+    this: &mut ResourceConstraints,
+    initial_size: usize,
+  );
+  fn v8__ResourceConstraints__initial_young_generation_size_in_bytes(
+    this: &ResourceConstraints,
+  ) -> usize;
+  fn v8__ResourceConstraints__set_initial_young_generation_size_in_bytes(
+    this: &mut ResourceConstraints,
+    initial_size: usize,
+  );
 }
 
 #[repr(C)]
@@ -62,10 +158,22 @@ impl Isolate {
   /// V8::initialize() must have run prior to this.
   #[allow(clippy::new_ret_no_self)]
   pub fn new(params: UniqueRef<CreateParams>) -> OwnedIsolate {
-    // TODO: support CreateParams.
     crate::V8::assert_initialized();
     let isolate_ptr = unsafe { v8__Isolate__New(params.into_raw()) };
-    OwnedIsolate(NonNull::new(isolate_ptr).unwrap())
+    let annex = Rc::new(IsolateAnnex {
+      isolate: Cell::new(NonNull::new(isolate_ptr)),
+      weak_data: Default::default(),
+    });
+    unsafe {
+      v8__Isolate__SetData(
+        isolate_ptr,
+        ANNEX_SLOT,
+        Rc::into_raw(annex) as *mut c_void,
+      )
+    };
+    OwnedIsolate {
+      isolate: NonNull::new(isolate_ptr).unwrap(),
+    }
   }
 
   /// Initial configuration parameters for a new Isolate.
@@ -73,10 +181,39 @@ impl Isolate {
     CreateParams::new()
   }
 
+  /// Returns the entered isolate for the current thread or None in
+  /// case there is no current isolate.
+  ///
+  /// This returns a raw pointer rather than a reference on purpose. The
+  /// isolate was entered elsewhere (possibly by C++ code) and nothing
+  /// available here borrows the IsolateScope or Locker that keeps it entered,
+  /// so any lifetime given to a reference would be made up: safe code could
+  /// hold on to it after the isolate is exited or disposed. The pointer is
+  /// only valid for as long as the isolate stays entered; dereferencing it is
+  /// up to the caller.
+  pub fn get_current() -> Option<NonNull<Isolate>> {
+    NonNull::new(unsafe { v8__Isolate__GetCurrent() })
+  }
+
+  /// Returns the annex that Isolate::new() attached to this isolate.
+  pub(crate) fn annex(&self) -> Rc<IsolateAnnex> {
+    let ptr = unsafe { v8__Isolate__GetData(self, ANNEX_SLOT) };
+    assert!(!ptr.is_null(), "isolate was not created by Isolate::new()");
+    let annex =
+      ManuallyDrop::new(unsafe { Rc::from_raw(ptr as *const IsolateAnnex) });
+    Rc::clone(&annex)
+  }
+
+  /// Check if this isolate is in use.
+  /// True if at least one thread Enter'ed this isolate.
+  pub fn is_in_use(&self) -> bool {
+    unsafe { v8__Isolate__IsInUse(self) }
+  }
+
   /// Sets this isolate as the entered one for the current thread.
   /// Saves the previously entered one (if any), so that it can be
   /// restored when exiting.  Re-entering an isolate is allowed.
-  pub fn enter(&mut self) {
+  fn enter(&mut self) {
     unsafe { v8__Isolate__Enter(self) }
   }
 
@@ -85,7 +222,7 @@ impl Isolate {
   /// entered more than once.
   ///
   /// Requires: self == Isolate::GetCurrent().
-  pub fn exit(&mut self) {
+  fn exit(&mut self) {
     unsafe { v8__Isolate__Exit(self) }
   }
 
@@ -144,24 +281,111 @@ impl AsMut<Isolate> for Isolate {
 }
 
 /// Same as Isolate but gets disposed when it goes out of scope.
-pub struct OwnedIsolate(NonNull<Isolate>);
+pub struct OwnedIsolate {
+  isolate: NonNull<Isolate>,
+}
 
 impl Drop for OwnedIsolate {
   fn drop(&mut self) {
-    unsafe { self.0.as_mut().dispose() }
+    debug_assert!(
+      !self.is_in_use(),
+      "OwnedIsolate dropped while it is still entered"
+    );
+    unsafe {
+      let isolate = self.isolate.as_mut();
+      let annex = v8__Isolate__GetData(isolate, ANNEX_SLOT);
+      let annex = Rc::from_raw(annex as *const IsolateAnnex);
+      dispose_weak_handles(isolate, &annex);
+      annex.isolate.set(None);
+      isolate.dispose();
+    }
+  }
+}
+
+// The isolate data slot in which Isolate::new() stores a pointer to the
+// isolate's IsolateAnnex.
+const ANNEX_SLOT: u32 = 0;
+
+// Rust-side state that is shared between an isolate and the handles created
+// in it. Handles that hold on to the annex can tell whether the isolate has
+// been disposed, in which case their storage cells have been freed with it.
+pub(crate) struct IsolateAnnex {
+  isolate: Cell<Option<NonNull<Isolate>>>,
+  // The weak handles whose weak callback has not run yet, keyed by the
+  // parameter that was passed to V8 along with the callback.
+  pub(crate) weak_data: RefCell<HashMap<*const WeakData, Rc<WeakData>>>,
+}
+
+impl IsolateAnnex {
+  /// Returns true if the isolate has not been disposed yet.
+  pub(crate) fn is_alive(&self) -> bool {
+    self.isolate.get().is_some()
+  }
+
+  /// Panics unless `isolate` is the (still alive) isolate this annex
+  /// belongs to.
+  pub(crate) fn assert_isolate(&self, isolate: &Isolate) {
+    let own = self.isolate.get().expect("isolate has been disposed");
+    assert!(
+      std::ptr::eq(own.as_ptr(), isolate),
+      "handle used with an isolate it does not belong to"
+    );
   }
 }
 
 impl Deref for OwnedIsolate {
   type Target = Isolate;
   fn deref(&self) -> &Self::Target {
-    unsafe { self.0.as_ref() }
+    unsafe { self.isolate.as_ref() }
   }
 }
 
 impl DerefMut for OwnedIsolate {
   fn deref_mut(&mut self) -> &mut Self::Target {
-    unsafe { self.0.as_mut() }
+    unsafe { self.isolate.as_mut() }
+  }
+}
+
+/// Stack-allocated class which sets the isolate for all operations
+/// executed within a local scope. The isolate is entered when the scope is
+/// created, and exited again when it is dropped.
+pub struct IsolateScope<'a>(&'a mut Isolate);
+
+impl<'a> IsolateScope<'a> {
+  pub fn new(isolate: &'a mut Isolate) -> Self {
+    isolate.enter();
+    Self(isolate)
+  }
+}
+
+impl<'a> Drop for IsolateScope<'a> {
+  fn drop(&mut self) {
+    self.0.exit()
+  }
+}
+
+impl<'a> Deref for IsolateScope<'a> {
+  type Target = Isolate;
+  fn deref(&self) -> &Self::Target {
+    self.0
+  }
+}
+
+impl<'a> DerefMut for IsolateScope<'a> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.0
+  }
+}
+
+impl<'a> AsRef<Isolate> for IsolateScope<'a> {
+  fn as_ref(&self) -> &Isolate {
+    self.0
+  }
+}
+
+impl<'a> AsMut<Isolate> for IsolateScope<'a> {
+  fn as_mut(&mut self) -> &mut Isolate {
+    self.0
   }
 }
 
@@ -181,6 +405,63 @@ impl CreateParams {
       )
     };
   }
+
+  /// ResourceConstraints to use for the new Isolate.
+  pub fn constraints(&mut self) -> &mut ResourceConstraints {
+    unsafe { &mut *v8__Isolate__CreateParams__CONSTRAINTS(self) }
+  }
+
+  /// External references to be used in deserializing the snapshot blob.
+  /// The list must be terminated by a null (zero) entry.
+  pub fn set_external_references(&mut self, value: &'static [isize]) {
+    assert_eq!(value.last(), Some(&0));
+    unsafe {
+      v8__Isolate__CreateParams__SET__external_references(self, value.as_ptr())
+    };
+  }
+
+  /// Enables the host application to provide a mechanism for recording
+  /// statistics counters.
+  pub fn set_counter_lookup_callback(&mut self, value: CounterLookupCallback) {
+    unsafe {
+      v8__Isolate__CreateParams__SET__counter_lookup_callback(self, value)
+    };
+  }
+
+  /// Enables the host application to provide a mechanism for recording
+  /// histograms. The CreateHistogram function returns a histogram which will
+  /// later be passed to the AddHistogramSample function.
+  pub fn set_create_histogram_callback(
+    &mut self,
+    value: CreateHistogramCallback,
+  ) {
+    unsafe {
+      v8__Isolate__CreateParams__SET__create_histogram_callback(self, value)
+    };
+  }
+
+  /// See `set_create_histogram_callback()`.
+  pub fn set_add_histogram_sample_callback(
+    &mut self,
+    value: AddHistogramSampleCallback,
+  ) {
+    unsafe {
+      v8__Isolate__CreateParams__SET__add_histogram_sample_callback(self, value)
+    };
+  }
+
+  /// Whether calling Atomics.wait (a function that may block) is allowed in
+  /// this isolate.
+  pub fn set_allow_atomics_wait(&mut self, value: bool) {
+    unsafe { v8__Isolate__CreateParams__SET__allow_atomics_wait(self, value) };
+  }
+
+  /// Termination is postponed when there is no active SafeForTerminationScope.
+  pub fn set_only_terminate_in_safe_scope(&mut self, value: bool) {
+    unsafe {
+      v8__Isolate__CreateParams__SET__only_terminate_in_safe_scope(self, value)
+    };
+  }
 }
 
 impl Delete for CreateParams {
@@ -188,3 +469,128 @@ impl Delete for CreateParams {
     unsafe { v8__Isolate__CreateParams__DELETE(self) }
   }
 }
+
+/// A container for an isolate's resource constraints, i.e. the sizes of the
+/// generations of the garbage collected heap.
+#[repr(C)]
+pub struct ResourceConstraints(Opaque);
+
+impl ResourceConstraints {
+  /// Configures the constraints with reasonable default values based on the
+  /// provided heap size limit. The heap size includes both the young and
+  /// the old generation.
+  ///
+  /// `initial_heap_size_in_bytes`: The initial heap size or zero.
+  /// By default V8 starts with a small heap and dynamically grows it to
+  /// match the set of live objects. This may lead to ineffective
+  /// garbage collections at startup if the live set is large.
+  /// Setting the initial heap size avoids such garbage collections.
+  /// Note that this does not affect young generation garbage collections.
+  ///
+  /// `maximum_heap_size_in_bytes`: The hard limit for the heap size.
+  /// When the heap size approaches this limit, V8 will perform series of
+  /// garbage collections and invoke the NearHeapLimitCallback. If the garbage
+  /// collections do not help and the callback does not increase the limit,
+  /// then V8 will crash with V8::FatalProcessOutOfMemory.
+  pub fn configure_defaults_from_heap_size(
+    &mut self,
+    initial_heap_size_in_bytes: usize,
+    maximum_heap_size_in_bytes: usize,
+  ) {
+    unsafe {
+      v8__ResourceConstraints__ConfigureDefaultsFromHeapSize(
+        self,
+        initial_heap_size_in_bytes,
+        maximum_heap_size_in_bytes,
+      )
+    }
+  }
+
+  /// Configures the constraints with reasonable default values based on the
+  /// capabilities of the current device the VM is running on.
+  ///
+  /// `physical_memory`: The total amount of physical memory on the current
+  /// device, in bytes.
+  /// `virtual_memory_limit`: The amount of virtual memory on the current
+  /// device, in bytes, or zero, if there is no limit.
+  pub fn configure_defaults(
+    &mut self,
+    physical_memory: u64,
+    virtual_memory_limit: u64,
+  ) {
+    unsafe {
+      v8__ResourceConstraints__ConfigureDefaults(
+        self,
+        physical_memory,
+        virtual_memory_limit,
+      )
+    }
+  }
+
+  /// The maximum size of the old generation.
+  /// When the old generation approaches this limit, V8 will perform series of
+  /// garbage collections and invoke the NearHeapLimitCallback.
+  /// If the garbage collections do not help and the callback does not
+  /// increase the limit, then V8 will crash with V8::FatalProcessOutOfMemory.
+  pub fn max_old_generation_size_in_bytes(&self) -> usize {
+    unsafe { v8__ResourceConstraints__max_old_generation_size_in_bytes(self) }
+  }
+
+  pub fn set_max_old_generation_size_in_bytes(&mut self, limit: usize) {
+    unsafe {
+      v8__ResourceConstraints__set_max_old_generation_size_in_bytes(self, limit)
+    }
+  }
+
+  /// The maximum size of the young generation, which consists of two
+  /// semi-spaces and a large object space. This affects frequency of
+  /// Scavenge garbage collections and should be typically much smaller that
+  /// the old generation.
+  pub fn max_young_generation_size_in_bytes(&self) -> usize {
+    unsafe { v8__ResourceConstraints__max_young_generation_size_in_bytes(self) }
+  }
+
+  pub fn set_max_young_generation_size_in_bytes(&mut self, limit: usize) {
+    unsafe {
+      v8__ResourceConstraints__set_max_young_generation_size_in_bytes(
+        self, limit,
+      )
+    }
+  }
+
+  pub fn initial_old_generation_size_in_bytes(&self) -> usize {
+    unsafe {
+      v8__ResourceConstraints__initial_old_generation_size_in_bytes(self)
+    }
+  }
+
+  pub fn set_initial_old_generation_size_in_bytes(
+    &mut self,
+    initial_size: usize,
+  ) {
+    unsafe {
+      v8__ResourceConstraints__set_initial_old_generation_size_in_bytes(
+        self,
+        initial_size,
+      )
+    }
+  }
+
+  pub fn initial_young_generation_size_in_bytes(&self) -> usize {
+    unsafe {
+      v8__ResourceConstraints__initial_young_generation_size_in_bytes(self)
+    }
+  }
+
+  pub fn set_initial_young_generation_size_in_bytes(
+    &mut self,
+    initial_size: usize,
+  ) {
+    unsafe {
+      v8__ResourceConstraints__set_initial_young_generation_size_in_bytes(
+        self,
+        initial_size,
+      )
+    }
+  }
+}
@@ -9,9 +9,12 @@ extern crate bitflags;
 extern crate lazy_static;
 extern crate libc;
 
+mod array_buffer_view;
 mod context;
 mod exception;
+mod external;
 mod function;
+mod global;
 mod handle_scope;
 mod isolate;
 mod local;
@@ -23,9 +26,12 @@ mod primitives;
 mod promise;
 mod property;
 mod script;
+mod shared_array_buffer;
 mod string;
 mod support;
+mod template;
 mod try_catch;
+mod typed_array;
 mod value;
 
 pub mod array_buffer;
@@ -38,27 +44,57 @@ pub mod script_compiler;
 #[allow(non_snake_case)]
 pub mod V8;
 
-pub use context::Context;
+pub use array_buffer::{ArrayBuffer, BackingStore};
+pub use array_buffer_view::{ArrayBufferView, DataView};
+pub use context::{Context, ContextScope};
 pub use exception::*;
+pub use external::External;
 pub use function::{
-  Function, FunctionCallbackInfo, FunctionTemplate, ReturnValue,
+  Function, FunctionCallbackArguments, FunctionCallbackInfo, FunctionTemplate,
+  ReturnValue,
 };
-pub use handle_scope::HandleScope;
+pub use global::{Global, Weak};
+pub use handle_scope::{EscapableHandleScope, HandleScope};
+pub use isolate::AddHistogramSampleCallback;
+pub use isolate::CounterLookupCallback;
+pub use isolate::CreateHistogramCallback;
+pub use isolate::CreateParams;
 pub use isolate::Isolate;
+pub use isolate::IsolateScope;
 pub use isolate::OwnedIsolate;
+pub use isolate::ResourceConstraints;
 pub use local::Local;
 pub use locker::Locker;
-pub use module::Module;
-pub use number::{Integer, Number};
-pub use object::Object;
+pub use module::{Module, ResolveCallback, Status as ModuleStatus};
+pub use number::{Int32, Integer, Number, Uint32};
+pub use object::{Array, Object};
 pub use primitives::*;
 pub use promise::{
   Promise, PromiseRejectEvent, PromiseRejectMessage, PromiseResolver,
   PromiseState,
 };
-pub use property::PropertyCallbackInfo;
+pub use property::{
+  AccessorNameGetterCallback, AccessorNameSetterCallback,
+  GenericNamedPropertyDeleterCallback, GenericNamedPropertyEnumeratorCallback,
+  GenericNamedPropertyGetterCallback, GenericNamedPropertyQueryCallback,
+  GenericNamedPropertySetterCallback, IndexedPropertyDeleterCallback,
+  IndexedPropertyEnumeratorCallback, IndexedPropertyGetterCallback,
+  IndexedPropertyQueryCallback, IndexedPropertySetterCallback,
+  PropertyAttribute, PropertyCallbackInfo,
+};
 pub use script::{Script, ScriptOrigin};
+pub use shared_array_buffer::SharedArrayBuffer;
 pub use string::NewStringType;
 pub use string::String;
+pub use support::SharedRef;
+pub use template::{
+  IndexedPropertyHandlerConfiguration, NamedPropertyHandlerConfiguration,
+  ObjectTemplate, Template,
+};
 pub use try_catch::TryCatch;
+pub use typed_array::{
+  BigInt64Array, Float64Array, Int32Array, TypedArray, Uint8Array,
+};
+pub use value::Data;
+pub use value::TryFromTypeError;
 pub use value::Value;
@@ -1,4 +1,31 @@
-use crate::value::Value;
+use crate::Array;
+use crate::ArrayBuffer;
+use crate::ArrayBufferView;
+use crate::BigInt64Array;
+use crate::Boolean;
+use crate::Data;
+use crate::DataView;
+use crate::External;
+use crate::Float64Array;
+use crate::Function;
+use crate::FunctionTemplate;
+use crate::Int32;
+use crate::Int32Array;
+use crate::Integer;
+use crate::Name;
+use crate::Number;
+use crate::Object;
+use crate::ObjectTemplate;
+use crate::Primitive;
+use crate::Promise;
+use crate::PromiseResolver;
+use crate::SharedArrayBuffer;
+use crate::String;
+use crate::Template;
+use crate::TypedArray;
+use crate::Uint32;
+use crate::Uint8Array;
+use crate::Value;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::ops::DerefMut;
@@ -67,17 +94,49 @@ impl<'sc, T> DerefMut for Local<'sc, T> {
   }
 }
 
-// TODO make it possible for targets other than Local<Value>. For example
-// Local<String> should be able to be down cast to Local<Name>.
-impl<'sc, T> From<Local<'sc, T>> for Local<'sc, Value>
-where
-  T: Deref<Target = Value>,
-{
-  fn from(v: Local<'sc, T>) -> Local<'sc, Value> {
-    unsafe { std::mem::transmute(v) }
-  }
+// Upcasts along the Deref chain, from each type to all of its ancestors.
+// Downcasts are checked and live in value.rs as `TryFrom` impls.
+macro_rules! impl_from {
+  ($source:ident => $($target:ident),+) => {
+    $(
+      impl<'sc> From<Local<'sc, $source>> for Local<'sc, $target> {
+        fn from(l: Local<'sc, $source>) -> Self {
+          let ptr = &*l as *const $source as *mut $target;
+          unsafe { Local::from_raw(ptr) }.unwrap()
+        }
+      }
+    )+
+  };
 }
 
+impl_from!(Value => Data);
+impl_from!(Template => Data);
+impl_from!(ObjectTemplate => Template, Data);
+impl_from!(FunctionTemplate => Template, Data);
+impl_from!(Object => Value, Data);
+impl_from!(Array => Object, Value, Data);
+impl_from!(Function => Object, Value, Data);
+impl_from!(Promise => Object, Value, Data);
+impl_from!(PromiseResolver => Object, Value, Data);
+impl_from!(ArrayBuffer => Object, Value, Data);
+impl_from!(SharedArrayBuffer => Object, Value, Data);
+impl_from!(ArrayBufferView => Object, Value, Data);
+impl_from!(DataView => ArrayBufferView, Object, Value, Data);
+impl_from!(TypedArray => ArrayBufferView, Object, Value, Data);
+impl_from!(Uint8Array => TypedArray, ArrayBufferView, Object, Value, Data);
+impl_from!(Int32Array => TypedArray, ArrayBufferView, Object, Value, Data);
+impl_from!(Float64Array => TypedArray, ArrayBufferView, Object, Value, Data);
+impl_from!(BigInt64Array => TypedArray, ArrayBufferView, Object, Value, Data);
+impl_from!(External => Value, Data);
+impl_from!(Primitive => Value, Data);
+impl_from!(Boolean => Primitive, Value, Data);
+impl_from!(Name => Primitive, Value, Data);
+impl_from!(String => Name, Primitive, Value, Data);
+impl_from!(Number => Primitive, Value, Data);
+impl_from!(Integer => Number, Primitive, Value, Data);
+impl_from!(Int32 => Integer, Number, Primitive, Value, Data);
+impl_from!(Uint32 => Integer, Number, Primitive, Value, Data);
+
 #[test]
 fn test_size_of_local() {
   use std::mem::size_of;
@@ -1,10 +1,81 @@
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::process;
+use std::ptr::null_mut;
+
 use crate::support::int;
+use crate::support::MaybeBool;
 use crate::support::Opaque;
 use crate::Context;
+use crate::HandleScope;
 use crate::Local;
+use crate::String;
 use crate::Value;
 
-extern "C" {}
+/// Called during Module::instantiate_module. Provided with arguments:
+/// (context, specifier, referrer). Return null on error.
+///
+/// Note: V8's ResolveCallback returns a MaybeLocal<Module>, which can't be
+/// expressed in the C ABI. The returned pointer is converted to a MaybeLocal
+/// by a trampoline in binding.cc.
+pub type ResolveCallback<'sc> = extern "C" fn(
+  Local<'sc, Context>,
+  Local<'sc, String>,
+  Local<'sc, Module>,
+) -> *mut Module;
+
+thread_local! {
+  // Points at the closure passed to the innermost active call to
+  // Module::instantiate_module_with() on this thread.
+  static RESOLVE_CLOSURE: Cell<*mut c_void> = const { Cell::new(null_mut()) };
+}
+
+extern "C" fn resolve_closure_trampoline<'sc, F>(
+  context: Local<'sc, Context>,
+  specifier: Local<'sc, String>,
+  referrer: Local<'sc, Module>,
+) -> *mut Module
+where
+  F: FnMut(
+    Local<'sc, Context>,
+    Local<'sc, String>,
+    Local<'sc, Module>,
+  ) -> Option<Local<'sc, Module>>,
+{
+  // Unwinding into V8 is not allowed, so a panic aborts the process instead.
+  let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    let closure = RESOLVE_CLOSURE.with(|c| c.get()) as *mut F;
+    assert!(!closure.is_null());
+    let closure = unsafe { &mut *closure };
+    match closure(context, specifier, referrer) {
+      Some(mut module) => &mut *module as *mut Module,
+      None => null_mut(),
+    }
+  }));
+  match result {
+    Ok(module) => module,
+    Err(_) => process::abort(),
+  }
+}
+
+extern "C" {
+  fn v8__Module__GetStatus(this: *const Module) -> Status;
+  fn v8__Module__GetException(this: *const Module) -> *mut Value;
+  fn v8__Module__GetModuleRequestsLength(this: *const Module) -> int;
+  fn v8__Module__GetModuleRequest(this: *const Module, i: int) -> *mut String;
+  fn v8__Module__GetIdentityHash(this: *const Module) -> int;
+  fn v8__Module__InstantiateModule(
+    this: *mut Module,
+    context: *mut Context,
+    callback: ResolveCallback,
+  ) -> MaybeBool;
+  fn v8__Module__Evaluate(
+    this: *mut Module,
+    context: *mut Context,
+  ) -> *mut Value;
+}
 
 /// The different states a module can be in.
 ///
@@ -22,35 +93,49 @@ pub enum Status {
   Errored,
 }
 
+/// A compiled JavaScript module.
 #[repr(C)]
 pub struct Module(Opaque);
 
-/// A compiled JavaScript module.
 impl Module {
   /// Returns the module's current status.
   pub fn get_status(&self) -> Status {
-    unimplemented!();
+    unsafe { v8__Module__GetStatus(self) }
   }
 
   /// For a module in kErrored status, this returns the corresponding exception.
-  pub fn get_exception(&self) -> Local<Value> {
-    unimplemented!();
+  /// Returns None for a module in any other status.
+  pub fn get_exception<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+  ) -> Option<Local<'sc, Value>> {
+    if self.get_status() != Status::Errored {
+      return None;
+    }
+    unsafe { Local::from_raw(v8__Module__GetException(self)) }
   }
 
   /// Returns the number of modules requested by this module.
-  pub fn get_module_requests_length(&self) -> int {
-    unimplemented!();
+  pub fn get_module_requests_length(&self) -> usize {
+    unsafe { v8__Module__GetModuleRequestsLength(self) as usize }
   }
 
   /// Returns the ith module specifier in this module.
   /// i must be < self.get_module_requests_length() and >= 0.
-  pub fn get_module_request(&self, _i: usize) -> Local<String> {
-    unimplemented!();
+  pub fn get_module_request<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    i: usize,
+  ) -> Local<'sc, String> {
+    assert!(i < self.get_module_requests_length());
+    unsafe {
+      Local::from_raw(v8__Module__GetModuleRequest(self, i as int)).unwrap()
+    }
   }
 
   /// Returns the identity hash for this object.
   pub fn get_identity_hash(&self) -> int {
-    unimplemented!();
+    unsafe { v8__Module__GetIdentityHash(self) }
   }
 
   /// Instantiates the module and its dependencies.
@@ -59,12 +144,44 @@ impl Module {
   /// instantiation. (In the case where the callback throws an exception, that
   /// exception is propagated.)
   #[must_use]
-  pub fn instantiate_module(
-    &self,
-    _context: Local<Context>,
-    _callback: Box<ResolveCallback>,
+  pub fn instantiate_module<'sc>(
+    &mut self,
+    mut context: Local<'sc, Context>,
+    callback: ResolveCallback<'sc>,
   ) -> Option<bool> {
-    unimplemented!();
+    unsafe {
+      v8__Module__InstantiateModule(self, &mut *context, callback).into()
+    }
+  }
+
+  /// Instantiates the module and its dependencies, using a Rust closure to
+  /// resolve the modules it imports.
+  ///
+  /// This is equivalent to `instantiate_module()`, except that the closure is
+  /// allowed to capture state, such as the embedder's module map. The closure
+  /// is only called during this call; it should return None on error. If the
+  /// closure panics, the process is aborted.
+  #[must_use]
+  pub fn instantiate_module_with<'sc, F>(
+    &mut self,
+    context: Local<'sc, Context>,
+    mut resolve: F,
+  ) -> Option<bool>
+  where
+    F: FnMut(
+      Local<'sc, Context>,
+      Local<'sc, String>,
+      Local<'sc, Module>,
+    ) -> Option<Local<'sc, Module>>,
+  {
+    // Modules may be instantiated recursively from within a closure, so the
+    // previously registered closure is restored afterwards.
+    let closure = &mut resolve as *mut F as *mut c_void;
+    let prev_closure = RESOLVE_CLOSURE.with(|c| c.replace(closure));
+    let result =
+      self.instantiate_module(context, resolve_closure_trampoline::<F>);
+    RESOLVE_CLOSURE.with(|c| c.set(prev_closure));
+    result
   }
 
   /// Evaluates the module and its dependencies.
@@ -74,13 +191,10 @@ impl Module {
   /// kErrored and propagate the thrown exception (which is then also available
   /// via |GetException|).
   #[must_use]
-  pub fn evaluate(&self, _context: Local<Context>) -> Option<Local<Value>> {
-    unimplemented!();
+  pub fn evaluate<'sc>(
+    &mut self,
+    mut context: Local<'sc, Context>,
+  ) -> Option<Local<'sc, Value>> {
+    unsafe { Local::from_raw(v8__Module__Evaluate(self, &mut *context)) }
   }
 }
-
-type ResolveCallback<'sc> = dyn Fn(
-  Local<'sc, Context>,
-  Local<'sc, String>,
-  Local<'sc, Module>,
-) -> Option<Local<'sc, Module>>;
@@ -2,9 +2,9 @@ use std::ops::Deref;
 
 use crate::isolate::Isolate;
 use crate::support::Opaque;
-use crate::value::Value;
 use crate::HandleScope;
 use crate::Local;
+use crate::Primitive;
 
 extern "C" {
   fn v8__Number__New(isolate: *mut Isolate, value: f64) -> *mut Number;
@@ -15,6 +15,8 @@ extern "C" {
     value: u32,
   ) -> *mut Integer;
   fn v8__Integer__Value(this: *const Integer) -> i64;
+  fn v8__Int32__Value(this: *const Int32) -> i32;
+  fn v8__Uint32__Value(this: *const Uint32) -> u32;
 }
 
 /// A JavaScript number value (ECMA-262, 4.3.20)
@@ -38,9 +40,9 @@ impl Number {
 }
 
 impl Deref for Number {
-  type Target = Value;
+  type Target = Primitive;
   fn deref(&self) -> &Self::Target {
-    unsafe { &*(self as *const _ as *const Value) }
+    unsafe { &*(self as *const _ as *const Primitive) }
   }
 }
 
@@ -80,3 +82,37 @@ impl Deref for Integer {
     unsafe { &*(self as *const _ as *const Number) }
   }
 }
+
+/// A JavaScript value representing a 32-bit signed integer.
+#[repr(C)]
+pub struct Int32(Opaque);
+
+impl Int32 {
+  pub fn value(&self) -> i32 {
+    unsafe { v8__Int32__Value(self) }
+  }
+}
+
+impl Deref for Int32 {
+  type Target = Integer;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Integer) }
+  }
+}
+
+/// A JavaScript value representing a 32-bit unsigned integer.
+#[repr(C)]
+pub struct Uint32(Opaque);
+
+impl Uint32 {
+  pub fn value(&self) -> u32 {
+    unsafe { v8__Uint32__Value(self) }
+  }
+}
+
+impl Deref for Uint32 {
+  type Target = Integer;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Integer) }
+  }
+}
@@ -1,10 +1,19 @@
+use std::convert::TryFrom;
+use std::ffi::c_void;
 use std::ops::Deref;
+use std::ptr::null_mut;
 
 use crate::isolate::Isolate;
+use crate::support::int;
+use crate::support::MaybeBool;
 use crate::support::Opaque;
+use crate::AccessorNameGetterCallback;
+use crate::AccessorNameSetterCallback;
+use crate::Context;
 use crate::HandleScope;
 use crate::Local;
 use crate::Name;
+use crate::PropertyAttribute;
 use crate::Value;
 
 /// A JavaScript object (ECMA-262, 4.3.3)
@@ -20,6 +29,103 @@ extern "C" {
     length: usize,
   ) -> *mut Object;
   fn v8__Object__GetIsolate(object: &Object) -> &mut Isolate;
+  fn v8__Object__Get(
+    object: &Object,
+    context: *mut Context,
+    key: *mut Value,
+  ) -> *mut Value;
+  fn v8__Object__GetIndex(
+    object: &Object,
+    context: *mut Context,
+    index: u32,
+  ) -> *mut Value;
+  fn v8__Object__Set(
+    object: &Object,
+    context: *mut Context,
+    key: *mut Value,
+    value: *mut Value,
+  ) -> MaybeBool;
+  fn v8__Object__SetIndex(
+    object: &Object,
+    context: *mut Context,
+    index: u32,
+    value: *mut Value,
+  ) -> MaybeBool;
+  fn v8__Object__Has(
+    object: &Object,
+    context: *mut Context,
+    key: *mut Value,
+  ) -> MaybeBool;
+  fn v8__Object__HasOwnProperty(
+    object: &Object,
+    context: *mut Context,
+    key: *mut Name,
+  ) -> MaybeBool;
+  fn v8__Object__Delete(
+    object: &Object,
+    context: *mut Context,
+    key: *mut Value,
+  ) -> MaybeBool;
+  fn v8__Object__CreateDataProperty(
+    object: &Object,
+    context: *mut Context,
+    key: *mut Name,
+    value: *mut Value,
+  ) -> MaybeBool;
+  fn v8__Object__DefineOwnProperty(
+    object: &Object,
+    context: *mut Context,
+    key: *mut Name,
+    value: *mut Value,
+    attr: PropertyAttribute,
+  ) -> MaybeBool;
+  fn v8__Object__GetPropertyNames(
+    object: &Object,
+    context: *mut Context,
+  ) -> *mut Array;
+  fn v8__Object__GetOwnPropertyNames(
+    object: &Object,
+    context: *mut Context,
+  ) -> *mut Array;
+  fn v8__Object__GetPrototype(object: &Object) -> *mut Value;
+  fn v8__Object__SetPrototype(
+    object: &Object,
+    context: *mut Context,
+    prototype: *mut Value,
+  ) -> MaybeBool;
+
+  fn v8__Object__SetAccessor(
+    object: &Object,
+    context: *mut Context,
+    key: *mut Name,
+    getter: AccessorNameGetterCallback,
+    setter: Option<AccessorNameSetterCallback>,
+    data: *mut Value,
+  ) -> MaybeBool;
+  fn v8__Object__InternalFieldCount(object: &Object) -> int;
+  fn v8__Object__GetInternalField(object: &Object, index: int) -> *mut Value;
+  fn v8__Object__SetInternalField(
+    object: &Object,
+    index: int,
+    value: *mut Value,
+  );
+  fn v8__Object__GetAlignedPointerFromInternalField(
+    object: &Object,
+    index: int,
+  ) -> *mut c_void;
+  fn v8__Object__SetAlignedPointerInInternalField(
+    object: &Object,
+    index: int,
+    value: *mut c_void,
+  );
+
+  fn v8__Array__New(isolate: *mut Isolate, length: int) -> *mut Array;
+  fn v8__Array__New__with_elements(
+    isolate: *mut Isolate,
+    elements: *const Local<Value>,
+    length: usize,
+  ) -> *mut Array;
+  fn v8__Array__Length(array: &Array) -> u32;
 }
 
 impl Object {
@@ -63,6 +169,293 @@ impl Object {
   pub fn get_isolate(&self) -> &Isolate {
     unsafe { v8__Object__GetIsolate(self) }
   }
+
+  /// Returns the value of the property `key`, as if by `object[key]` in JS.
+  /// Returns None if an exception was thrown, e.g. by a getter.
+  pub fn get<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<Context>,
+    mut key: Local<Value>,
+  ) -> Option<Local<'sc, Value>> {
+    unsafe { Local::from_raw(v8__Object__Get(self, &mut *context, &mut *key)) }
+  }
+
+  /// Returns the value of the element at `index`.
+  pub fn get_index<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<Context>,
+    index: u32,
+  ) -> Option<Local<'sc, Value>> {
+    unsafe { Local::from_raw(v8__Object__GetIndex(self, &mut *context, index)) }
+  }
+
+  /// Sets the property `key` to `value`, as if by `object[key] = value` in
+  /// JS. Returns None if an exception was thrown, e.g. by a setter.
+  pub fn set(
+    &self,
+    mut context: Local<Context>,
+    mut key: Local<Value>,
+    mut value: Local<Value>,
+  ) -> Option<bool> {
+    unsafe { v8__Object__Set(self, &mut *context, &mut *key, &mut *value) }
+      .into()
+  }
+
+  /// Sets the element at `index` to `value`.
+  pub fn set_index(
+    &self,
+    mut context: Local<Context>,
+    index: u32,
+    mut value: Local<Value>,
+  ) -> Option<bool> {
+    unsafe { v8__Object__SetIndex(self, &mut *context, index, &mut *value) }
+      .into()
+  }
+
+  /// Returns true if the object or its prototype chain has the property
+  /// `key`, as if by `key in object` in JS.
+  pub fn has(
+    &self,
+    mut context: Local<Context>,
+    mut key: Local<Value>,
+  ) -> Option<bool> {
+    unsafe { v8__Object__Has(self, &mut *context, &mut *key) }.into()
+  }
+
+  /// Returns true if the object itself has the property `key`, as if by
+  /// `Object.prototype.hasOwnProperty.call(object, key)` in JS.
+  pub fn has_own_property(
+    &self,
+    mut context: Local<Context>,
+    mut key: Local<Name>,
+  ) -> Option<bool> {
+    unsafe { v8__Object__HasOwnProperty(self, &mut *context, &mut *key) }.into()
+  }
+
+  /// Deletes the property `key`, as if by `delete object[key]` in JS.
+  pub fn delete(
+    &self,
+    mut context: Local<Context>,
+    mut key: Local<Value>,
+  ) -> Option<bool> {
+    unsafe { v8__Object__Delete(self, &mut *context, &mut *key) }.into()
+  }
+
+  /// Implements CreateDataProperty (ECMA-262, 7.3.4).
+  ///
+  /// Defines a configurable, writable, enumerable property with the given
+  /// value on the object unless the property already exists and is not
+  /// configurable or the object is not extensible.
+  ///
+  /// Returns true on success.
+  pub fn create_data_property(
+    &self,
+    mut context: Local<Context>,
+    mut key: Local<Name>,
+    mut value: Local<Value>,
+  ) -> Option<bool> {
+    unsafe {
+      v8__Object__CreateDataProperty(
+        self,
+        &mut *context,
+        &mut *key,
+        &mut *value,
+      )
+    }
+    .into()
+  }
+
+  /// Implements DefineOwnProperty.
+  ///
+  /// In general, CreateDataProperty will be faster, however, does not allow
+  /// for specifying attributes.
+  ///
+  /// Returns true on success.
+  pub fn define_own_property(
+    &self,
+    mut context: Local<Context>,
+    mut key: Local<Name>,
+    mut value: Local<Value>,
+    attr: PropertyAttribute,
+  ) -> Option<bool> {
+    unsafe {
+      v8__Object__DefineOwnProperty(
+        self,
+        &mut *context,
+        &mut *key,
+        &mut *value,
+        attr,
+      )
+    }
+    .into()
+  }
+
+  /// Returns an array containing the names of the enumerable properties
+  /// of this object, including properties from prototype objects. The
+  /// array returned by this method contains the same values as would
+  /// be enumerated by a for-in statement over this object.
+  pub fn get_property_names<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<Context>,
+  ) -> Option<Local<'sc, Array>> {
+    unsafe {
+      Local::from_raw(v8__Object__GetPropertyNames(self, &mut *context))
+    }
+  }
+
+  /// This function has the same functionality as GetPropertyNames but the
+  /// returned array doesn't contain the names of properties from prototype
+  /// objects.
+  pub fn get_own_property_names<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<Context>,
+  ) -> Option<Local<'sc, Array>> {
+    unsafe {
+      Local::from_raw(v8__Object__GetOwnPropertyNames(self, &mut *context))
+    }
+  }
+
+  /// Get the prototype object. This does not skip objects marked to
+  /// be skipped by __proto__ and it does not consult the security
+  /// handler.
+  pub fn get_prototype<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+  ) -> Option<Local<'sc, Value>> {
+    unsafe { Local::from_raw(v8__Object__GetPrototype(self)) }
+  }
+
+  /// Set the prototype object. This does not skip objects marked to
+  /// be skipped by __proto__ and it does not consult the security
+  /// handler.
+  pub fn set_prototype(
+    &self,
+    mut context: Local<Context>,
+    mut prototype: Local<Value>,
+  ) -> Option<bool> {
+    unsafe { v8__Object__SetPrototype(self, &mut *context, &mut *prototype) }
+      .into()
+  }
+
+  /// Installs an accessor for the property `key` on this object. Whenever the
+  /// property is read, `getter` is called.
+  pub fn set_accessor(
+    &self,
+    context: Local<Context>,
+    key: Local<Name>,
+    getter: AccessorNameGetterCallback,
+  ) -> Option<bool> {
+    self.set_accessor_impl(context, key, getter, None, null_mut())
+  }
+
+  /// Like `set_accessor()`, but `setter` is called whenever the property
+  /// `key` is assigned to.
+  pub fn set_accessor_with_setter(
+    &self,
+    context: Local<Context>,
+    key: Local<Name>,
+    getter: AccessorNameGetterCallback,
+    setter: AccessorNameSetterCallback,
+  ) -> Option<bool> {
+    self.set_accessor_impl(context, key, getter, Some(setter), null_mut())
+  }
+
+  /// Like `set_accessor()`, but the callbacks can retrieve `data` with
+  /// `PropertyCallbackInfo::data()`.
+  pub fn set_accessor_with_data(
+    &self,
+    context: Local<Context>,
+    key: Local<Name>,
+    getter: AccessorNameGetterCallback,
+    setter: Option<AccessorNameSetterCallback>,
+    mut data: Local<Value>,
+  ) -> Option<bool> {
+    self.set_accessor_impl(context, key, getter, setter, &mut *data)
+  }
+
+  fn set_accessor_impl(
+    &self,
+    mut context: Local<Context>,
+    mut key: Local<Name>,
+    getter: AccessorNameGetterCallback,
+    setter: Option<AccessorNameSetterCallback>,
+    data: *mut Value,
+  ) -> Option<bool> {
+    unsafe {
+      v8__Object__SetAccessor(
+        self,
+        &mut *context,
+        &mut *key,
+        getter,
+        setter,
+        data,
+      )
+    }
+    .into()
+  }
+
+  /// Gets the number of internal fields for this Object.
+  ///
+  /// The internal field accessors below panic if they are passed an index
+  /// that is not less than this count.
+  pub fn internal_field_count(&self) -> usize {
+    let count = unsafe { v8__Object__InternalFieldCount(self) };
+    usize::try_from(count).unwrap()
+  }
+
+  /// Gets the value from an internal field.
+  pub fn get_internal_field<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    index: usize,
+  ) -> Local<'sc, Value> {
+    assert!(index < self.internal_field_count(), "index out of bounds");
+    let index = int::try_from(index).unwrap();
+    unsafe {
+      Local::from_raw(v8__Object__GetInternalField(self, index)).unwrap()
+    }
+  }
+
+  /// Sets the value in an internal field.
+  pub fn set_internal_field(&self, index: usize, mut value: Local<Value>) {
+    assert!(index < self.internal_field_count(), "index out of bounds");
+    let index = int::try_from(index).unwrap();
+    unsafe { v8__Object__SetInternalField(self, index, &mut *value) };
+  }
+
+  /// Gets a 2-byte-aligned native pointer from an internal field.
+  ///
+  /// The field must have been set with
+  /// `set_aligned_pointer_in_internal_field()`, otherwise the returned pointer
+  /// is garbage.
+  pub unsafe fn get_aligned_pointer_from_internal_field(
+    &self,
+    index: usize,
+  ) -> *mut c_void {
+    assert!(index < self.internal_field_count(), "index out of bounds");
+    let index = int::try_from(index).unwrap();
+    v8__Object__GetAlignedPointerFromInternalField(self, index)
+  }
+
+  /// Sets a 2-byte-aligned native pointer in an internal field. Panics if the
+  /// pointer is not aligned.
+  ///
+  /// The pointer is opaque to V8: the memory it points to is neither kept
+  /// alive nor freed by V8.
+  pub unsafe fn set_aligned_pointer_in_internal_field(
+    &self,
+    index: usize,
+    value: *mut c_void,
+  ) {
+    assert!(index < self.internal_field_count(), "index out of bounds");
+    assert_eq!(value as usize & 1, 0, "pointer is not 2-byte aligned");
+    let index = int::try_from(index).unwrap();
+    v8__Object__SetAlignedPointerInInternalField(self, index, value)
+  }
 }
 
 impl Deref for Object {
@@ -71,3 +464,44 @@ impl Deref for Object {
     unsafe { &*(self as *const _ as *const Value) }
   }
 }
+
+/// An instance of the built-in array constructor (ECMA-262, 15.4.2).
+#[repr(C)]
+pub struct Array(Opaque);
+
+impl Array {
+  /// Creates a JavaScript array with the given length. If the length
+  /// is negative the returned array will have length 0.
+  pub fn new<'sc>(
+    scope: &mut HandleScope<'sc>,
+    length: i32,
+  ) -> Local<'sc, Array> {
+    unsafe { Local::from_raw(v8__Array__New(scope.as_mut(), length)) }.unwrap()
+  }
+
+  /// Creates a JavaScript array out of a slice of elements.
+  pub fn new_with_elements<'sc>(
+    scope: &mut HandleScope<'sc>,
+    elements: &[Local<Value>],
+  ) -> Local<'sc, Array> {
+    unsafe {
+      Local::from_raw(v8__Array__New__with_elements(
+        scope.as_mut(),
+        elements.as_ptr(),
+        elements.len(),
+      ))
+    }
+    .unwrap()
+  }
+
+  pub fn length(&self) -> u32 {
+    unsafe { v8__Array__Length(self) }
+  }
+}
+
+impl Deref for Array {
+  type Target = Object;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Object) }
+  }
+}
@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::ops::Deref;
 
 use crate::support::MaybeBool;
 use crate::support::Opaque;
@@ -6,6 +7,7 @@ use crate::Context;
 use crate::Function;
 use crate::HandleScope;
 use crate::Local;
+use crate::Object;
 use crate::Value;
 
 extern "C" {
@@ -142,6 +144,13 @@ impl Promise {
   }
 }
 
+impl Deref for Promise {
+  type Target = Object;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Object) }
+  }
+}
+
 #[repr(C)]
 pub struct PromiseResolver(Opaque);
 
@@ -191,6 +200,13 @@ impl PromiseResolver {
   }
 }
 
+impl Deref for PromiseResolver {
+  type Target = Object;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Object) }
+  }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub enum PromiseRejectEvent {
@@ -1,8 +1,15 @@
 use crate::isolate::Isolate;
+use crate::support::int;
 use crate::support::Opaque;
+use crate::Array;
+use crate::Boolean;
+use crate::Integer;
 use crate::Local;
+use crate::Name;
 use crate::Object;
 use crate::ReturnValue;
+use crate::Value;
+use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 
 extern "C" {
@@ -11,29 +18,145 @@ extern "C" {
   ) -> &mut Isolate;
   fn v8__PropertyCallbackInfo__This(info: &PropertyCallbackInfo)
     -> *mut Object;
+  fn v8__PropertyCallbackInfo__Holder(
+    info: &PropertyCallbackInfo,
+  ) -> *mut Object;
+  fn v8__PropertyCallbackInfo__Data(info: &PropertyCallbackInfo) -> *mut Value;
   fn v8__PropertyCallbackInfo__GetReturnValue(
     info: &PropertyCallbackInfo,
     out: *mut ReturnValue,
   );
 }
 
+bitflags! {
+  /// The attributes of a property, as used by `Object::define_own_property`.
+  #[derive(Default)]
+  #[repr(transparent)]
+  pub struct PropertyAttribute: int {
+    /// None.
+    const NONE = 0;
+    /// ReadOnly, i.e., not writable.
+    const READ_ONLY = 1;
+    /// DontEnum, i.e., not enumerable.
+    const DONT_ENUM = 2;
+    /// DontDelete, i.e., not configurable.
+    const DONT_DELETE = 4;
+  }
+}
+
+/// Accessor callback, invoked when the property `name` is read. The result
+/// is set on the callback's return value.
+pub type AccessorNameGetterCallback =
+  extern "C" fn(name: Local<Name>, info: &PropertyCallbackInfo);
+
+/// Accessor callback, invoked when `value` is assigned to the property
+/// `name`.
+pub type AccessorNameSetterCallback = extern "C" fn(
+  name: Local<Name>,
+  value: Local<Value>,
+  info: &PropertyCallbackInfo,
+);
+
+/// Interceptor for get requests on an object. Intercepts the request if the
+/// return value is set, otherwise it falls through to the object.
+pub type GenericNamedPropertyGetterCallback =
+  extern "C" fn(property: Local<Name>, info: &PropertyCallbackInfo);
+
+/// Interceptor for set requests on an object. Intercepts the request if the
+/// return value is set, otherwise it falls through to the object.
+pub type GenericNamedPropertySetterCallback = extern "C" fn(
+  property: Local<Name>,
+  value: Local<Value>,
+  info: &PropertyCallbackInfo,
+);
+
+/// Intercepts all requests that query the attributes of the property, e.g.
+/// `getOwnPropertyDescriptor()` and `propertyIsEnumerable()`. To intercept,
+/// set the return value to an Integer holding the `PropertyAttribute` bits.
+pub type GenericNamedPropertyQueryCallback =
+  extern "C" fn(property: Local<Name>, info: &PropertyCallbackInfo<Integer>);
+
+/// Interceptor for delete requests on an object. To intercept, set the return
+/// value to a Boolean that is true if the property was deleted.
+pub type GenericNamedPropertyDeleterCallback =
+  extern "C" fn(property: Local<Name>, info: &PropertyCallbackInfo<Boolean>);
+
+/// Returns an array containing the names of the properties the named
+/// property getter intercepts, e.g. for `Object.keys()` and `for..in`.
+pub type GenericNamedPropertyEnumeratorCallback =
+  extern "C" fn(info: &PropertyCallbackInfo<Array>);
+
+/// See `GenericNamedPropertyGetterCallback`.
+pub type IndexedPropertyGetterCallback =
+  extern "C" fn(index: u32, info: &PropertyCallbackInfo);
+
+/// See `GenericNamedPropertySetterCallback`.
+pub type IndexedPropertySetterCallback =
+  extern "C" fn(index: u32, value: Local<Value>, info: &PropertyCallbackInfo);
+
+/// See `GenericNamedPropertyQueryCallback`.
+pub type IndexedPropertyQueryCallback =
+  extern "C" fn(index: u32, info: &PropertyCallbackInfo<Integer>);
+
+/// See `GenericNamedPropertyDeleterCallback`.
+pub type IndexedPropertyDeleterCallback =
+  extern "C" fn(index: u32, info: &PropertyCallbackInfo<Boolean>);
+
+/// See `GenericNamedPropertyEnumeratorCallback`.
+pub type IndexedPropertyEnumeratorCallback =
+  extern "C" fn(info: &PropertyCallbackInfo<Array>);
+
+/// The information passed to a property callback. Like in V8, `T` is the type
+/// of the value that the callback can return.
 #[repr(C)]
-pub struct PropertyCallbackInfo(Opaque);
+pub struct PropertyCallbackInfo<T = Value>(Opaque, PhantomData<T>);
 
-impl PropertyCallbackInfo {
-  pub fn get_return_value(&self) -> ReturnValue {
-    let mut rv = MaybeUninit::<ReturnValue>::uninit();
+impl<T> PropertyCallbackInfo<T> {
+  pub fn get_return_value(&self) -> ReturnValue<T> {
+    let mut rv = MaybeUninit::<ReturnValue<T>>::uninit();
     unsafe {
-      v8__PropertyCallbackInfo__GetReturnValue(self, rv.as_mut_ptr());
+      v8__PropertyCallbackInfo__GetReturnValue(
+        self.as_value_info(),
+        rv.as_mut_ptr() as *mut ReturnValue,
+      );
       rv.assume_init()
     }
   }
 
   pub fn get_isolate(&self) -> &Isolate {
-    unsafe { v8__PropertyCallbackInfo__GetIsolate(self) }
+    unsafe { v8__PropertyCallbackInfo__GetIsolate(self.as_value_info()) }
   }
 
   pub fn this(&self) -> Local<Object> {
-    unsafe { Local::from_raw(v8__PropertyCallbackInfo__This(self)).unwrap() }
+    unsafe {
+      Local::from_raw(v8__PropertyCallbackInfo__This(self.as_value_info()))
+        .unwrap()
+    }
+  }
+
+  /// The object in the prototype chain of the receiver that has the
+  /// accessor or interceptor that is being invoked. For accessors installed
+  /// with `Object::set_accessor()` this is the object it was installed on,
+  /// whereas `this()` may be an object inheriting from it.
+  pub fn holder(&self) -> Local<'_, Object> {
+    unsafe {
+      Local::from_raw(v8__PropertyCallbackInfo__Holder(self.as_value_info()))
+        .unwrap()
+    }
+  }
+
+  /// The data set in the configuration, i.e., in
+  /// `Object::set_accessor_with_data()`. Undefined if no data was set.
+  pub fn data(&self) -> Local<'_, Value> {
+    unsafe {
+      Local::from_raw(v8__PropertyCallbackInfo__Data(self.as_value_info()))
+        .unwrap()
+    }
+  }
+
+  // The layout of v8::PropertyCallbackInfo<T> does not depend on T, so the
+  // bindings are only instantiated for PropertyCallbackInfo<Value>.
+  fn as_value_info(&self) -> &PropertyCallbackInfo {
+    unsafe { &*(self as *const Self as *const PropertyCallbackInfo) }
   }
 }
@@ -5,7 +5,7 @@ use std::ptr::null;
 use crate::support::Opaque;
 use crate::Boolean;
 use crate::Context;
-use crate::HandleScope;
+use crate::ContextScope;
 use crate::Integer;
 use crate::Local;
 use crate::String;
@@ -45,13 +45,12 @@ pub struct Script(Opaque);
 impl Script {
   /// A shorthand for ScriptCompiler::Compile().
   pub fn compile<'sc>(
-    _scope: &mut HandleScope<'sc>,
-    mut context: Local<Context>,
+    scope: &mut ContextScope<'_, 'sc>,
     mut source: Local<String>,
     origin: Option<&ScriptOrigin>,
   ) -> Option<Local<'sc, Script>> {
-    // TODO: use the type system to enforce that a Context has been entered.
-    // TODO: `context` and `source` probably shouldn't be mut.
+    // TODO: `source` probably shouldn't be mut.
+    let mut context = scope.context();
     unsafe {
       Local::from_raw(v8__Script__Compile(
         &mut *context,
@@ -66,9 +65,9 @@ impl Script {
   /// UnboundScript::BindToCurrentContext()).
   pub fn run<'sc>(
     &mut self,
-    _scope: &mut HandleScope<'sc>,
-    mut context: Local<Context>,
+    scope: &mut ContextScope<'_, 'sc>,
   ) -> Option<Local<'sc, Value>> {
+    let mut context = scope.context();
     unsafe { Local::from_raw(v8__Script__Run(self, &mut *context)) }
   }
 }
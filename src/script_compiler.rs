@@ -1,5 +1,6 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 //! For compiling scripts.
+use crate::HandleScope;
 use crate::Isolate;
 use crate::Local;
 use crate::Module;
@@ -76,15 +77,15 @@ pub enum NoCacheReason {
 ///
 /// Corresponds to the ParseModule abstract operation in the ECMAScript
 /// specification.
-pub fn compile_module(
-  isolate: &Isolate,
+pub fn compile_module<'sc>(
+  scope: &mut HandleScope<'sc>,
   source: Source,
   options: CompileOptions,
   no_cache_reason: NoCacheReason,
-) -> Option<Local<Module>> {
+) -> Option<Local<'sc, Module>> {
   unsafe {
     Local::from_raw(v8__ScriptCompiler__CompileModule(
-      isolate,
+      scope.as_mut(),
       &source,
       options,
       no_cache_reason,
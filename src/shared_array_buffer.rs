@@ -0,0 +1,155 @@
+use std::ffi::c_void;
+use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::ptr::null_mut;
+
+use crate::array_buffer::boxed_slice_deleter_callback;
+use crate::array_buffer::BackingStoreDeleterCallback;
+use crate::isolate::Isolate;
+use crate::support::Opaque;
+use crate::support::SharedRef;
+use crate::BackingStore;
+use crate::HandleScope;
+use crate::Local;
+use crate::Object;
+
+extern "C" {
+  fn v8__SharedArrayBuffer__New__with_byte_length(
+    isolate: *mut Isolate,
+    byte_length: usize,
+  ) -> *mut SharedArrayBuffer;
+  fn v8__SharedArrayBuffer__New__with_backing_store(
+    isolate: *mut Isolate,
+    backing_store: *const SharedRef<BackingStore>,
+  ) -> *mut SharedArrayBuffer;
+  fn v8__SharedArrayBuffer__ByteLength(this: *const SharedArrayBuffer)
+    -> usize;
+  fn v8__SharedArrayBuffer__GetBackingStore(
+    this: *mut SharedArrayBuffer,
+    out: &mut MaybeUninit<SharedRef<BackingStore>>,
+  );
+  fn v8__SharedArrayBuffer__NewBackingStore__with_byte_length(
+    isolate: *mut Isolate,
+    byte_length: usize,
+    out: &mut MaybeUninit<SharedRef<BackingStore>>,
+  );
+  fn v8__SharedArrayBuffer__NewBackingStore__with_data(
+    data: *mut c_void,
+    byte_length: usize,
+    deleter: BackingStoreDeleterCallback,
+    deleter_data: *mut c_void,
+    out: &mut MaybeUninit<SharedRef<BackingStore>>,
+  );
+}
+
+/// An instance of the built-in SharedArrayBuffer constructor.
+#[repr(C)]
+pub struct SharedArrayBuffer(Opaque);
+
+impl SharedArrayBuffer {
+  /// Create a new SharedArrayBuffer. Allocate |byte_length| bytes.
+  /// Allocated memory will be owned by a created SharedArrayBuffer and
+  /// will be deallocated when it is garbage-collected,
+  /// unless the object is externalized.
+  pub fn new<'sc>(
+    scope: &mut HandleScope<'sc>,
+    byte_length: usize,
+  ) -> Local<'sc, SharedArrayBuffer> {
+    unsafe {
+      Local::from_raw(v8__SharedArrayBuffer__New__with_byte_length(
+        scope.as_mut(),
+        byte_length,
+      ))
+    }
+    .unwrap()
+  }
+
+  /// Create a new SharedArrayBuffer with an existing backing store.
+  /// The created array keeps a reference to the backing store until the array
+  /// is garbage collected.
+  pub fn new_with_backing_store<'sc>(
+    scope: &mut HandleScope<'sc>,
+    backing_store: &SharedRef<BackingStore>,
+  ) -> Local<'sc, SharedArrayBuffer> {
+    unsafe {
+      Local::from_raw(v8__SharedArrayBuffer__New__with_backing_store(
+        scope.as_mut(),
+        backing_store,
+      ))
+    }
+    .unwrap()
+  }
+
+  /// Data length in bytes.
+  pub fn byte_length(&self) -> usize {
+    unsafe { v8__SharedArrayBuffer__ByteLength(self) }
+  }
+
+  /// Get a shared pointer to the backing store of this array buffer. This
+  /// pointer coordinates the lifetime management of the internal storage
+  /// with any live ArrayBuffers on the heap, even across isolates. The
+  /// embedder should not attempt to manage lifetime of the storage through
+  /// other means.
+  pub fn get_backing_store(&mut self) -> SharedRef<BackingStore> {
+    let mut buf = MaybeUninit::uninit();
+    unsafe {
+      v8__SharedArrayBuffer__GetBackingStore(self, &mut buf);
+      buf.assume_init()
+    }
+  }
+
+  /// Returns a new standalone BackingStore that is allocated using the array
+  /// buffer allocator of the isolate. The result can be later passed to
+  /// SharedArrayBuffer::new_with_backing_store().
+  pub fn new_backing_store(
+    scope: &mut HandleScope,
+    byte_length: usize,
+  ) -> SharedRef<BackingStore> {
+    let mut buf = MaybeUninit::uninit();
+    unsafe {
+      v8__SharedArrayBuffer__NewBackingStore__with_byte_length(
+        scope.as_mut(),
+        byte_length,
+        &mut buf,
+      );
+      buf.assume_init()
+    }
+  }
+
+  /// Returns a new standalone BackingStore that takes over the ownership of
+  /// the given buffer. The memory is freed when the last reference to the
+  /// backing store is dropped, which may happen on any thread.
+  ///
+  /// The result can be later passed to
+  /// SharedArrayBuffer::new_with_backing_store().
+  pub fn new_backing_store_from_boxed_slice(
+    data: Box<[u8]>,
+  ) -> SharedRef<BackingStore> {
+    let byte_length = data.len();
+    let data_ptr = Box::into_raw(data) as *mut u8 as *mut c_void;
+    let mut buf = MaybeUninit::uninit();
+    unsafe {
+      v8__SharedArrayBuffer__NewBackingStore__with_data(
+        data_ptr,
+        byte_length,
+        boxed_slice_deleter_callback,
+        null_mut(),
+        &mut buf,
+      );
+      buf.assume_init()
+    }
+  }
+
+  /// Same as `new_backing_store_from_boxed_slice()`, but takes a Vec. Any
+  /// excess capacity of the Vec is released first.
+  pub fn new_backing_store_from_vec(data: Vec<u8>) -> SharedRef<BackingStore> {
+    Self::new_backing_store_from_boxed_slice(data.into_boxed_slice())
+  }
+}
+
+impl Deref for SharedArrayBuffer {
+  type Target = Object;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Object) }
+  }
+}
@@ -10,7 +10,7 @@ use crate::support::int;
 use crate::support::Opaque;
 use crate::HandleScope;
 use crate::Local;
-use crate::Value;
+use crate::Name;
 
 extern "C" {
   fn v8__String__NewFromUtf8(
@@ -147,8 +147,8 @@ impl String {
 }
 
 impl Deref for String {
-  type Target = Value;
+  type Target = Name;
   fn deref(&self) -> &Self::Target {
-    unsafe { &*(self as *const _ as *const Value) }
+    unsafe { &*(self as *const _ as *const Name) }
   }
 }
@@ -70,6 +70,23 @@ inline static v8::MaybeLocal<T> ptr_to_maybe_local(T* ptr) {
   static_assert(sizeof(v8::MaybeLocal<T>) == sizeof(T*), "");
   return *reinterpret_cast<v8::MaybeLocal<T>*>(&ptr);
 }
+
+// Note: a v8::Global<T> is just a pointer to a storage cell that is owned by
+// the isolate, which makes it possible to transfer it to and from Rust as a
+// raw pointer.
+template <class T>
+inline static T* global_to_ptr(v8::Global<T>& global) {
+  static_assert(sizeof(v8::Global<T>) == sizeof(T*), "");
+  T* ptr = nullptr;
+  std::swap(ptr, reinterpret_cast<T*&>(global));
+  return ptr;
+}
+
+template <class T>
+inline static v8::Global<T>& ptr_to_global(T*& ptr) {
+  static_assert(sizeof(v8::Global<T>) == sizeof(T*), "");
+  return reinterpret_cast<v8::Global<T>&>(ptr);
+}
 }  // namespace support
 
 #endif  // SUPPORT_H_
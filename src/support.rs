@@ -8,6 +8,7 @@ use std::ptr::NonNull;
 
 pub use std::os::raw::c_char as char;
 pub use std::os::raw::c_int as int;
+pub use std::os::raw::c_long as long;
 
 pub type Opaque = [usize; 0];
 
@@ -137,6 +138,66 @@ where
   }
 }
 
+pub trait Shared
+where
+  Self: Sized + 'static,
+{
+  fn clone(shared_ref: &SharedRef<Self>) -> SharedRef<Self>;
+  fn get(shared_ref: &SharedRef<Self>) -> *mut Self;
+  fn reset(shared_ref: &mut SharedRef<Self>);
+  fn use_count(shared_ref: &SharedRef<Self>) -> long;
+}
+
+/// Wrapper around a C++ std::shared_ptr that is known not to be null.
+/// A shared_ptr consists of a pointer to the object and a pointer to its
+/// control block, and can be moved around freely as long as neither is
+/// touched; cloning and dropping the SharedRef is delegated to C++.
+#[repr(C)]
+#[derive(Debug)]
+pub struct SharedRef<T>([usize; 2], PhantomData<T>)
+where
+  T: Shared;
+
+unsafe impl<T> Send for SharedRef<T> where T: Shared + Sync {}
+unsafe impl<T> Sync for SharedRef<T> where T: Shared + Sync {}
+
+impl<T> SharedRef<T>
+where
+  T: Shared,
+{
+  pub fn use_count(&self) -> long {
+    <T as Shared>::use_count(self)
+  }
+}
+
+impl<T> Clone for SharedRef<T>
+where
+  T: Shared,
+{
+  fn clone(&self) -> Self {
+    <T as Shared>::clone(self)
+  }
+}
+
+impl<T> Deref for SharedRef<T>
+where
+  T: Shared,
+{
+  type Target = T;
+  fn deref(&self) -> &T {
+    unsafe { &*<T as Shared>::get(self) }
+  }
+}
+
+impl<T> Drop for SharedRef<T>
+where
+  T: Shared,
+{
+  fn drop(&mut self) {
+    <T as Shared>::reset(self);
+  }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub enum MaybeBool {
@@ -155,6 +216,25 @@ impl Into<Option<bool>> for MaybeBool {
   }
 }
 
+/// The C-ABI compatible equivalent of V8's `Maybe<T>`, for values that are
+/// returned through an out-parameter.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Maybe<T> {
+  has_value: bool,
+  value: T,
+}
+
+impl<T> From<Maybe<T>> for Option<T> {
+  fn from(maybe: Maybe<T>) -> Self {
+    if maybe.has_value {
+      Some(maybe.value)
+    } else {
+      None
+    }
+  }
+}
+
 #[derive(Copy, Clone, Debug)]
 #[repr(transparent)]
 pub struct CxxVTable(pub *const Opaque);
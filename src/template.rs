@@ -0,0 +1,251 @@
+use crate::isolate::Isolate;
+use crate::support::int;
+use crate::support::Opaque;
+use crate::AccessorNameGetterCallback;
+use crate::AccessorNameSetterCallback;
+use crate::Context;
+use crate::Data;
+use crate::FunctionTemplate;
+use crate::GenericNamedPropertyDeleterCallback;
+use crate::GenericNamedPropertyEnumeratorCallback;
+use crate::GenericNamedPropertyGetterCallback;
+use crate::GenericNamedPropertyQueryCallback;
+use crate::GenericNamedPropertySetterCallback;
+use crate::HandleScope;
+use crate::IndexedPropertyDeleterCallback;
+use crate::IndexedPropertyEnumeratorCallback;
+use crate::IndexedPropertyGetterCallback;
+use crate::IndexedPropertyQueryCallback;
+use crate::IndexedPropertySetterCallback;
+use crate::Local;
+use crate::Name;
+use crate::Object;
+use crate::PropertyAttribute;
+use std::convert::TryFrom;
+use std::ops::Deref;
+use std::ptr::null_mut;
+
+extern "C" {
+  fn v8__Template__Set(
+    this: &Template,
+    key: *mut Name,
+    value: *mut Data,
+    attr: PropertyAttribute,
+  );
+  fn v8__Template__SetAccessorProperty(
+    this: &Template,
+    key: *mut Name,
+    getter: *mut FunctionTemplate,
+    setter: *mut FunctionTemplate,
+    attr: PropertyAttribute,
+  );
+
+  fn v8__ObjectTemplate__New(isolate: *mut Isolate) -> *mut ObjectTemplate;
+  fn v8__ObjectTemplate__NewInstance(
+    this: &ObjectTemplate,
+    context: *mut Context,
+  ) -> *mut Object;
+  fn v8__ObjectTemplate__InternalFieldCount(this: &ObjectTemplate) -> int;
+  fn v8__ObjectTemplate__SetInternalFieldCount(
+    this: &ObjectTemplate,
+    value: int,
+  );
+  fn v8__ObjectTemplate__SetAccessor(
+    this: &ObjectTemplate,
+    key: *mut Name,
+    getter: AccessorNameGetterCallback,
+    setter: Option<AccessorNameSetterCallback>,
+  );
+  fn v8__ObjectTemplate__SetNamedPropertyHandler(
+    this: &ObjectTemplate,
+    getter: Option<GenericNamedPropertyGetterCallback>,
+    setter: Option<GenericNamedPropertySetterCallback>,
+    query: Option<GenericNamedPropertyQueryCallback>,
+    deleter: Option<GenericNamedPropertyDeleterCallback>,
+    enumerator: Option<GenericNamedPropertyEnumeratorCallback>,
+  );
+  fn v8__ObjectTemplate__SetIndexedPropertyHandler(
+    this: &ObjectTemplate,
+    getter: Option<IndexedPropertyGetterCallback>,
+    setter: Option<IndexedPropertySetterCallback>,
+    query: Option<IndexedPropertyQueryCallback>,
+    deleter: Option<IndexedPropertyDeleterCallback>,
+    enumerator: Option<IndexedPropertyEnumeratorCallback>,
+  );
+}
+
+/// The superclass of object and function templates.
+#[repr(C)]
+pub struct Template(Opaque);
+
+impl Template {
+  /// Adds a property to each instance created by this template. The value
+  /// must be a primitive or a template; objects can't be shared between
+  /// instances.
+  pub fn set(&self, key: Local<Name>, value: Local<Data>) {
+    self.set_with_attr(key, value, PropertyAttribute::NONE)
+  }
+
+  /// Like `set()`, but with the given property attributes.
+  pub fn set_with_attr(
+    &self,
+    mut key: Local<Name>,
+    mut value: Local<Data>,
+    attr: PropertyAttribute,
+  ) {
+    unsafe { v8__Template__Set(self, &mut *key, &mut *value, attr) }
+  }
+
+  /// Adds an accessor property to each instance created by this template,
+  /// implemented by JavaScript functions created from the getter and setter
+  /// templates. At least one of them should be given.
+  pub fn set_accessor_property(
+    &self,
+    mut key: Local<Name>,
+    getter: Option<Local<FunctionTemplate>>,
+    setter: Option<Local<FunctionTemplate>>,
+    attr: PropertyAttribute,
+  ) {
+    let getter = getter.map_or(null_mut(), |mut l| &mut *l);
+    let setter = setter.map_or(null_mut(), |mut l| &mut *l);
+    unsafe {
+      v8__Template__SetAccessorProperty(self, &mut *key, getter, setter, attr)
+    }
+  }
+}
+
+/// The callbacks of a named property interceptor, see
+/// `ObjectTemplate::set_named_property_handler()`. Callbacks that are None
+/// don't intercept the corresponding operation.
+#[derive(Clone, Copy, Default)]
+pub struct NamedPropertyHandlerConfiguration {
+  pub getter: Option<GenericNamedPropertyGetterCallback>,
+  pub setter: Option<GenericNamedPropertySetterCallback>,
+  pub query: Option<GenericNamedPropertyQueryCallback>,
+  pub deleter: Option<GenericNamedPropertyDeleterCallback>,
+  pub enumerator: Option<GenericNamedPropertyEnumeratorCallback>,
+}
+
+/// The callbacks of an indexed property interceptor, see
+/// `ObjectTemplate::set_indexed_property_handler()`. Callbacks that are None
+/// don't intercept the corresponding operation.
+#[derive(Clone, Copy, Default)]
+pub struct IndexedPropertyHandlerConfiguration {
+  pub getter: Option<IndexedPropertyGetterCallback>,
+  pub setter: Option<IndexedPropertySetterCallback>,
+  pub query: Option<IndexedPropertyQueryCallback>,
+  pub deleter: Option<IndexedPropertyDeleterCallback>,
+  pub enumerator: Option<IndexedPropertyEnumeratorCallback>,
+}
+
+/// An ObjectTemplate is used to create objects at runtime.
+///
+/// Properties added to an ObjectTemplate are added to each object
+/// created from the ObjectTemplate.
+#[repr(C)]
+pub struct ObjectTemplate(Opaque);
+
+impl ObjectTemplate {
+  /// Creates an ObjectTemplate.
+  pub fn new<'sc>(scope: &mut HandleScope<'sc>) -> Local<'sc, ObjectTemplate> {
+    unsafe { Local::from_raw(v8__ObjectTemplate__New(scope.as_mut())) }.unwrap()
+  }
+
+  /// Creates a new instance of this template.
+  pub fn new_instance<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<Context>,
+  ) -> Option<Local<'sc, Object>> {
+    unsafe {
+      Local::from_raw(v8__ObjectTemplate__NewInstance(self, &mut *context))
+    }
+  }
+
+  /// Gets the number of internal fields for objects generated from
+  /// this template.
+  pub fn internal_field_count(&self) -> usize {
+    let count = unsafe { v8__ObjectTemplate__InternalFieldCount(self) };
+    usize::try_from(count).unwrap()
+  }
+
+  /// Sets the number of internal fields for objects generated from
+  /// this template.
+  pub fn set_internal_field_count(&self, value: usize) {
+    let value = int::try_from(value).unwrap();
+    unsafe { v8__ObjectTemplate__SetInternalFieldCount(self, value) }
+  }
+
+  /// Sets an accessor on each object instance created from this template.
+  /// Whenever the property `key` is read, `getter` is called.
+  pub fn set_accessor(
+    &self,
+    mut key: Local<Name>,
+    getter: AccessorNameGetterCallback,
+  ) {
+    unsafe { v8__ObjectTemplate__SetAccessor(self, &mut *key, getter, None) }
+  }
+
+  /// Like `set_accessor()`, but `setter` is called whenever the property
+  /// `key` is assigned to.
+  pub fn set_accessor_with_setter(
+    &self,
+    mut key: Local<Name>,
+    getter: AccessorNameGetterCallback,
+    setter: AccessorNameSetterCallback,
+  ) {
+    unsafe {
+      v8__ObjectTemplate__SetAccessor(self, &mut *key, getter, Some(setter))
+    }
+  }
+
+  /// Sets a named property handler on the object template.
+  ///
+  /// Whenever a property whose name is a string or a symbol is accessed on
+  /// objects created from this object template, the provided callback is
+  /// invoked instead of accessing the property directly on the JavaScript
+  /// object.
+  pub fn set_named_property_handler(
+    &self,
+    config: NamedPropertyHandlerConfiguration,
+  ) {
+    unsafe {
+      v8__ObjectTemplate__SetNamedPropertyHandler(
+        self,
+        config.getter,
+        config.setter,
+        config.query,
+        config.deleter,
+        config.enumerator,
+      )
+    }
+  }
+
+  /// Sets an indexed property handler on the object template.
+  ///
+  /// Whenever an indexed property is accessed on objects created from this
+  /// object template, the provided callback is invoked instead of accessing
+  /// the property directly on the JavaScript object.
+  pub fn set_indexed_property_handler(
+    &self,
+    config: IndexedPropertyHandlerConfiguration,
+  ) {
+    unsafe {
+      v8__ObjectTemplate__SetIndexedPropertyHandler(
+        self,
+        config.getter,
+        config.setter,
+        config.query,
+        config.deleter,
+        config.enumerator,
+      )
+    }
+  }
+}
+
+impl Deref for ObjectTemplate {
+  type Target = Template;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const Template) }
+  }
+}
@@ -0,0 +1,132 @@
+use std::ops::Deref;
+
+use crate::support::Opaque;
+use crate::ArrayBuffer;
+use crate::ArrayBufferView;
+use crate::HandleScope;
+use crate::Local;
+
+extern "C" {
+  fn v8__TypedArray__Length(this: *mut TypedArray) -> usize;
+  fn v8__Uint8Array__New(
+    buffer: *mut ArrayBuffer,
+    byte_offset: usize,
+    length: usize,
+  ) -> *mut Uint8Array;
+  fn v8__Int32Array__New(
+    buffer: *mut ArrayBuffer,
+    byte_offset: usize,
+    length: usize,
+  ) -> *mut Int32Array;
+  fn v8__Float64Array__New(
+    buffer: *mut ArrayBuffer,
+    byte_offset: usize,
+    length: usize,
+  ) -> *mut Float64Array;
+  fn v8__BigInt64Array__New(
+    buffer: *mut ArrayBuffer,
+    byte_offset: usize,
+    length: usize,
+  ) -> *mut BigInt64Array;
+}
+
+/// A base class for an instance of TypedArray series of constructors
+/// (ES6 draft 15.13.6).
+#[repr(C)]
+pub struct TypedArray(Opaque);
+
+impl TypedArray {
+  /// Number of elements in this typed array
+  /// (e.g. for Int16Array, |ByteLength|/2).
+  pub fn length(&self) -> usize {
+    unsafe { v8__TypedArray__Length(self as *const Self as *mut Self) }
+  }
+}
+
+impl Deref for TypedArray {
+  type Target = ArrayBufferView;
+  fn deref(&self) -> &Self::Target {
+    unsafe { &*(self as *const _ as *const ArrayBufferView) }
+  }
+}
+
+// Returns true if a typed array with `length` elements of `element_size`
+// bytes, starting at `byte_offset`, fits in a buffer of `buffer_length` bytes.
+fn is_valid_range(
+  buffer_length: usize,
+  byte_offset: usize,
+  length: usize,
+  element_size: usize,
+) -> bool {
+  if byte_offset.checked_rem(element_size) != Some(0) {
+    return false;
+  }
+  length
+    .checked_mul(element_size)
+    .and_then(|byte_length| byte_offset.checked_add(byte_length))
+    .map(|end| end <= buffer_length)
+    .unwrap_or(false)
+}
+
+macro_rules! typed_array {
+  ($name:ident, $new:ident, $element_size:expr, $doc:expr) => {
+    #[doc = $doc]
+    #[repr(C)]
+    pub struct $name(Opaque);
+
+    impl $name {
+      /// Creates a typed array with `length` elements over `buffer`,
+      /// starting at `byte_offset`. Returns None if `byte_offset` is not a
+      /// multiple of the element size, or if the range does not fit in the
+      /// buffer.
+      pub fn new<'sc>(
+        _scope: &mut HandleScope<'sc>,
+        mut buffer: Local<ArrayBuffer>,
+        byte_offset: usize,
+        length: usize,
+      ) -> Option<Local<'sc, $name>> {
+        if !is_valid_range(
+          buffer.byte_length(),
+          byte_offset,
+          length,
+          $element_size,
+        ) {
+          return None;
+        }
+        unsafe { Local::from_raw($new(&mut *buffer, byte_offset, length)) }
+      }
+    }
+
+    impl Deref for $name {
+      type Target = TypedArray;
+      fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const _ as *const TypedArray) }
+      }
+    }
+  };
+}
+
+typed_array!(
+  Uint8Array,
+  v8__Uint8Array__New,
+  1,
+  "An instance of Uint8Array constructor (ES6 draft 15.13.6)."
+);
+typed_array!(
+  Int32Array,
+  v8__Int32Array__New,
+  4,
+  "An instance of Int32Array constructor (ES6 draft 15.13.6)."
+);
+typed_array!(
+  Float64Array,
+  v8__Float64Array__New,
+  8,
+  "An instance of Float64Array constructor (ES6 draft 15.13.6)."
+);
+typed_array!(
+  BigInt64Array,
+  v8__BigInt64Array__New,
+  8,
+  "An instance of BigInt64Array constructor."
+);
@@ -1,11 +1,141 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::mem::MaybeUninit;
+
+use crate::isolate::Isolate;
 use crate::support;
+use crate::support::Maybe;
+use crate::support::MaybeBool;
+use crate::Array;
+use crate::ArrayBuffer;
+use crate::ArrayBufferView;
+use crate::BigInt64Array;
+use crate::Boolean;
+use crate::Context;
+use crate::DataView;
+use crate::External;
+use crate::Float64Array;
+use crate::Function;
+use crate::HandleScope;
+use crate::Int32;
+use crate::Int32Array;
+use crate::Integer;
+use crate::Local;
+use crate::Name;
+use crate::Number;
+use crate::Object;
+use crate::Primitive;
+use crate::Promise;
+use crate::SharedArrayBuffer;
+use crate::String;
+use crate::TypedArray;
+use crate::Uint32;
+use crate::Uint8Array;
 
 extern "C" {
   fn v8__Value__IsUndefined(this: &Value) -> bool;
   fn v8__Value__IsNull(this: &Value) -> bool;
   fn v8__Value__IsNullOrUndefined(this: &Value) -> bool;
+  fn v8__Value__IsTrue(this: &Value) -> bool;
+  fn v8__Value__IsFalse(this: &Value) -> bool;
+  fn v8__Value__IsName(this: &Value) -> bool;
+  fn v8__Value__IsString(this: &Value) -> bool;
+  fn v8__Value__IsSymbol(this: &Value) -> bool;
+  fn v8__Value__IsFunction(this: &Value) -> bool;
+  fn v8__Value__IsArray(this: &Value) -> bool;
+  fn v8__Value__IsObject(this: &Value) -> bool;
+  fn v8__Value__IsBigInt(this: &Value) -> bool;
+  fn v8__Value__IsBoolean(this: &Value) -> bool;
+  fn v8__Value__IsNumber(this: &Value) -> bool;
+  fn v8__Value__IsExternal(this: &Value) -> bool;
+  fn v8__Value__IsInt32(this: &Value) -> bool;
+  fn v8__Value__IsUint32(this: &Value) -> bool;
+  fn v8__Value__IsDate(this: &Value) -> bool;
+  fn v8__Value__IsArgumentsObject(this: &Value) -> bool;
+  fn v8__Value__IsBigIntObject(this: &Value) -> bool;
+  fn v8__Value__IsBooleanObject(this: &Value) -> bool;
+  fn v8__Value__IsNumberObject(this: &Value) -> bool;
+  fn v8__Value__IsStringObject(this: &Value) -> bool;
+  fn v8__Value__IsSymbolObject(this: &Value) -> bool;
+  fn v8__Value__IsNativeError(this: &Value) -> bool;
+  fn v8__Value__IsRegExp(this: &Value) -> bool;
+  fn v8__Value__IsAsyncFunction(this: &Value) -> bool;
+  fn v8__Value__IsGeneratorFunction(this: &Value) -> bool;
+  fn v8__Value__IsGeneratorObject(this: &Value) -> bool;
+  fn v8__Value__IsPromise(this: &Value) -> bool;
+  fn v8__Value__IsMap(this: &Value) -> bool;
+  fn v8__Value__IsSet(this: &Value) -> bool;
+  fn v8__Value__IsMapIterator(this: &Value) -> bool;
+  fn v8__Value__IsSetIterator(this: &Value) -> bool;
+  fn v8__Value__IsWeakMap(this: &Value) -> bool;
+  fn v8__Value__IsWeakSet(this: &Value) -> bool;
+  fn v8__Value__IsArrayBuffer(this: &Value) -> bool;
+  fn v8__Value__IsArrayBufferView(this: &Value) -> bool;
+  fn v8__Value__IsTypedArray(this: &Value) -> bool;
+  fn v8__Value__IsUint8Array(this: &Value) -> bool;
+  fn v8__Value__IsUint8ClampedArray(this: &Value) -> bool;
+  fn v8__Value__IsInt8Array(this: &Value) -> bool;
+  fn v8__Value__IsUint16Array(this: &Value) -> bool;
+  fn v8__Value__IsInt16Array(this: &Value) -> bool;
+  fn v8__Value__IsUint32Array(this: &Value) -> bool;
+  fn v8__Value__IsInt32Array(this: &Value) -> bool;
+  fn v8__Value__IsFloat32Array(this: &Value) -> bool;
+  fn v8__Value__IsFloat64Array(this: &Value) -> bool;
+  fn v8__Value__IsBigInt64Array(this: &Value) -> bool;
+  fn v8__Value__IsBigUint64Array(this: &Value) -> bool;
+  fn v8__Value__IsDataView(this: &Value) -> bool;
+  fn v8__Value__IsSharedArrayBuffer(this: &Value) -> bool;
+  fn v8__Value__IsProxy(this: &Value) -> bool;
+  fn v8__Value__IsWebAssemblyCompiledModule(this: &Value) -> bool;
+  fn v8__Value__IsModuleNamespaceObject(this: &Value) -> bool;
+  fn v8__Value__ToString(this: &Value, context: *mut Context) -> *mut String;
+  fn v8__Value__ToDetailString(
+    this: &Value,
+    context: *mut Context,
+  ) -> *mut String;
+  fn v8__Value__ToNumber(this: &Value, context: *mut Context) -> *mut Number;
+  fn v8__Value__ToInteger(this: &Value, context: *mut Context) -> *mut Integer;
+  fn v8__Value__ToUint32(this: &Value, context: *mut Context) -> *mut Uint32;
+  fn v8__Value__ToInt32(this: &Value, context: *mut Context) -> *mut Int32;
+  fn v8__Value__ToObject(this: &Value, context: *mut Context) -> *mut Object;
+  fn v8__Value__ToBoolean(this: &Value, isolate: *mut Isolate) -> *mut Boolean;
+  fn v8__Value__BooleanValue(this: &Value, isolate: *mut Isolate) -> bool;
+  fn v8__Value__NumberValue(
+    this: &Value,
+    context: *mut Context,
+    out: *mut Maybe<f64>,
+  );
+  fn v8__Value__IntegerValue(
+    this: &Value,
+    context: *mut Context,
+    out: *mut Maybe<i64>,
+  );
+  fn v8__Value__Uint32Value(
+    this: &Value,
+    context: *mut Context,
+    out: *mut Maybe<u32>,
+  );
+  fn v8__Value__Int32Value(
+    this: &Value,
+    context: *mut Context,
+    out: *mut Maybe<i32>,
+  );
+  fn v8__Value__Equals(
+    this: &Value,
+    context: *mut Context,
+    that: *mut Value,
+  ) -> MaybeBool;
+  fn v8__Value__StrictEquals(this: &Value, that: *mut Value) -> bool;
+  fn v8__Value__SameValue(this: &Value, that: *mut Value) -> bool;
+  fn v8__Value__TypeOf(this: &Value, isolate: *mut Isolate) -> *mut String;
 }
 
+/// The superclass of objects that can reside on V8's heap, i.e. JavaScript
+/// values and templates.
+#[repr(C)]
+pub struct Data(support::Opaque);
+
 /// The superclass of all JavaScript values and objects.
 #[repr(C)]
 pub struct Value(support::Opaque);
@@ -26,4 +156,511 @@ impl Value {
   pub fn is_null_or_undefined(&self) -> bool {
     unsafe { v8__Value__IsNullOrUndefined(self) }
   }
+
+  /// Returns true if this value is true.
+  /// This is not the same as `BooleanValue()`. The latter performs a
+  /// conversion to boolean, i.e. the result of `Boolean(value)` in JS, whereas
+  /// this checks `value === true`.
+  pub fn is_true(&self) -> bool {
+    unsafe { v8__Value__IsTrue(self) }
+  }
+
+  /// Returns true if this value is false.
+  /// This is not the same as `!BooleanValue()`. The latter performs a
+  /// conversion to boolean, i.e. the result of `!Boolean(value)` in JS, whereas
+  /// this checks `value === false`.
+  pub fn is_false(&self) -> bool {
+    unsafe { v8__Value__IsFalse(self) }
+  }
+
+  /// Returns true if this value is a symbol or a string.
+  /// This is equivalent to
+  /// `typeof value === 'string' || typeof value === 'symbol'` in JS.
+  pub fn is_name(&self) -> bool {
+    unsafe { v8__Value__IsName(self) }
+  }
+
+  /// Returns true if this value is an instance of the String type.
+  /// See ECMA-262 8.4.
+  pub fn is_string(&self) -> bool {
+    unsafe { v8__Value__IsString(self) }
+  }
+
+  /// Returns true if this value is a symbol.
+  /// This is equivalent to `typeof value === 'symbol'` in JS.
+  pub fn is_symbol(&self) -> bool {
+    unsafe { v8__Value__IsSymbol(self) }
+  }
+
+  /// Returns true if this value is a function.
+  pub fn is_function(&self) -> bool {
+    unsafe { v8__Value__IsFunction(self) }
+  }
+
+  /// Returns true if this value is an array. Note that it will return false for
+  /// an Proxy for an array.
+  pub fn is_array(&self) -> bool {
+    unsafe { v8__Value__IsArray(self) }
+  }
+
+  /// Returns true if this value is an object.
+  pub fn is_object(&self) -> bool {
+    unsafe { v8__Value__IsObject(self) }
+  }
+
+  /// Returns true if this value is a bigint.
+  /// This is equivalent to `typeof value === 'bigint'` in JS.
+  pub fn is_big_int(&self) -> bool {
+    unsafe { v8__Value__IsBigInt(self) }
+  }
+
+  /// Returns true if this value is boolean.
+  /// This is equivalent to `typeof value === 'boolean'` in JS.
+  pub fn is_boolean(&self) -> bool {
+    unsafe { v8__Value__IsBoolean(self) }
+  }
+
+  /// Returns true if this value is a number.
+  /// This is equivalent to `typeof value === 'number'` in JS.
+  pub fn is_number(&self) -> bool {
+    unsafe { v8__Value__IsNumber(self) }
+  }
+
+  /// Returns true if this value is an `External` object.
+  pub fn is_external(&self) -> bool {
+    unsafe { v8__Value__IsExternal(self) }
+  }
+
+  /// Returns true if this value is a 32-bit signed integer.
+  pub fn is_int32(&self) -> bool {
+    unsafe { v8__Value__IsInt32(self) }
+  }
+
+  /// Returns true if this value is a 32-bit unsigned integer.
+  pub fn is_uint32(&self) -> bool {
+    unsafe { v8__Value__IsUint32(self) }
+  }
+
+  /// Returns true if this value is a Date.
+  pub fn is_date(&self) -> bool {
+    unsafe { v8__Value__IsDate(self) }
+  }
+
+  /// Returns true if this value is an Arguments object.
+  pub fn is_arguments_object(&self) -> bool {
+    unsafe { v8__Value__IsArgumentsObject(self) }
+  }
+
+  /// Returns true if this value is a BigInt object.
+  pub fn is_big_int_object(&self) -> bool {
+    unsafe { v8__Value__IsBigIntObject(self) }
+  }
+
+  /// Returns true if this value is a Boolean object.
+  pub fn is_boolean_object(&self) -> bool {
+    unsafe { v8__Value__IsBooleanObject(self) }
+  }
+
+  /// Returns true if this value is a Number object.
+  pub fn is_number_object(&self) -> bool {
+    unsafe { v8__Value__IsNumberObject(self) }
+  }
+
+  /// Returns true if this value is a String object.
+  pub fn is_string_object(&self) -> bool {
+    unsafe { v8__Value__IsStringObject(self) }
+  }
+
+  /// Returns true if this value is a Symbol object.
+  pub fn is_symbol_object(&self) -> bool {
+    unsafe { v8__Value__IsSymbolObject(self) }
+  }
+
+  /// Returns true if this value is a NativeError.
+  pub fn is_native_error(&self) -> bool {
+    unsafe { v8__Value__IsNativeError(self) }
+  }
+
+  /// Returns true if this value is a RegExp.
+  pub fn is_reg_exp(&self) -> bool {
+    unsafe { v8__Value__IsRegExp(self) }
+  }
+
+  /// Returns true if this value is an async function.
+  pub fn is_async_function(&self) -> bool {
+    unsafe { v8__Value__IsAsyncFunction(self) }
+  }
+
+  /// Returns true if this value is a Generator function.
+  pub fn is_generator_function(&self) -> bool {
+    unsafe { v8__Value__IsGeneratorFunction(self) }
+  }
+
+  /// Returns true if this value is a Generator object (iterator).
+  pub fn is_generator_object(&self) -> bool {
+    unsafe { v8__Value__IsGeneratorObject(self) }
+  }
+
+  /// Returns true if this value is a Promise.
+  pub fn is_promise(&self) -> bool {
+    unsafe { v8__Value__IsPromise(self) }
+  }
+
+  /// Returns true if this value is a Map.
+  pub fn is_map(&self) -> bool {
+    unsafe { v8__Value__IsMap(self) }
+  }
+
+  /// Returns true if this value is a Set.
+  pub fn is_set(&self) -> bool {
+    unsafe { v8__Value__IsSet(self) }
+  }
+
+  /// Returns true if this value is a Map Iterator.
+  pub fn is_map_iterator(&self) -> bool {
+    unsafe { v8__Value__IsMapIterator(self) }
+  }
+
+  /// Returns true if this value is a Set Iterator.
+  pub fn is_set_iterator(&self) -> bool {
+    unsafe { v8__Value__IsSetIterator(self) }
+  }
+
+  /// Returns true if this value is a WeakMap.
+  pub fn is_weak_map(&self) -> bool {
+    unsafe { v8__Value__IsWeakMap(self) }
+  }
+
+  /// Returns true if this value is a WeakSet.
+  pub fn is_weak_set(&self) -> bool {
+    unsafe { v8__Value__IsWeakSet(self) }
+  }
+
+  /// Returns true if this value is an ArrayBuffer.
+  pub fn is_array_buffer(&self) -> bool {
+    unsafe { v8__Value__IsArrayBuffer(self) }
+  }
+
+  /// Returns true if this value is an ArrayBufferView.
+  pub fn is_array_buffer_view(&self) -> bool {
+    unsafe { v8__Value__IsArrayBufferView(self) }
+  }
+
+  /// Returns true if this value is one of TypedArrays.
+  pub fn is_typed_array(&self) -> bool {
+    unsafe { v8__Value__IsTypedArray(self) }
+  }
+
+  /// Returns true if this value is an Uint8Array.
+  pub fn is_uint8_array(&self) -> bool {
+    unsafe { v8__Value__IsUint8Array(self) }
+  }
+
+  /// Returns true if this value is an Uint8ClampedArray.
+  pub fn is_uint8_clamped_array(&self) -> bool {
+    unsafe { v8__Value__IsUint8ClampedArray(self) }
+  }
+
+  /// Returns true if this value is an Int8Array.
+  pub fn is_int8_array(&self) -> bool {
+    unsafe { v8__Value__IsInt8Array(self) }
+  }
+
+  /// Returns true if this value is an Uint16Array.
+  pub fn is_uint16_array(&self) -> bool {
+    unsafe { v8__Value__IsUint16Array(self) }
+  }
+
+  /// Returns true if this value is an Int16Array.
+  pub fn is_int16_array(&self) -> bool {
+    unsafe { v8__Value__IsInt16Array(self) }
+  }
+
+  /// Returns true if this value is an Uint32Array.
+  pub fn is_uint32_array(&self) -> bool {
+    unsafe { v8__Value__IsUint32Array(self) }
+  }
+
+  /// Returns true if this value is an Int32Array.
+  pub fn is_int32_array(&self) -> bool {
+    unsafe { v8__Value__IsInt32Array(self) }
+  }
+
+  /// Returns true if this value is a Float32Array.
+  pub fn is_float32_array(&self) -> bool {
+    unsafe { v8__Value__IsFloat32Array(self) }
+  }
+
+  /// Returns true if this value is a Float64Array.
+  pub fn is_float64_array(&self) -> bool {
+    unsafe { v8__Value__IsFloat64Array(self) }
+  }
+
+  /// Returns true if this value is a BigInt64Array.
+  pub fn is_big_int64_array(&self) -> bool {
+    unsafe { v8__Value__IsBigInt64Array(self) }
+  }
+
+  /// Returns true if this value is a BigUint64Array.
+  pub fn is_big_uint64_array(&self) -> bool {
+    unsafe { v8__Value__IsBigUint64Array(self) }
+  }
+
+  /// Returns true if this value is a DataView.
+  pub fn is_data_view(&self) -> bool {
+    unsafe { v8__Value__IsDataView(self) }
+  }
+
+  /// Returns true if this value is a SharedArrayBuffer.
+  /// This is an experimental feature.
+  pub fn is_shared_array_buffer(&self) -> bool {
+    unsafe { v8__Value__IsSharedArrayBuffer(self) }
+  }
+
+  /// Returns true if this value is a JavaScript Proxy.
+  pub fn is_proxy(&self) -> bool {
+    unsafe { v8__Value__IsProxy(self) }
+  }
+
+  /// Returns true if this value is a WebAssembly.Module.
+  pub fn is_web_assembly_compiled_module(&self) -> bool {
+    unsafe { v8__Value__IsWebAssemblyCompiledModule(self) }
+  }
+
+  /// Returns true if the value is a Module Namespace Object.
+  pub fn is_module_namespace_object(&self) -> bool {
+    unsafe { v8__Value__IsModuleNamespaceObject(self) }
+  }
+
+  /// Converts this value to a string, as if by calling `String(value)` in JS.
+  /// Returns None if an exception was thrown during the conversion.
+  pub fn to_string<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<'sc, Context>,
+  ) -> Option<Local<'sc, String>> {
+    unsafe { Local::from_raw(v8__Value__ToString(self, &mut *context)) }
+  }
+
+  /// Converts this value to a string that is suitable for use in error
+  /// messages. Unlike `to_string()`, this also succeeds for Symbols. It may
+  /// still run user code, such as a custom `toString()` method.
+  pub fn to_detail_string<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<'sc, Context>,
+  ) -> Option<Local<'sc, String>> {
+    unsafe { Local::from_raw(v8__Value__ToDetailString(self, &mut *context)) }
+  }
+
+  /// Converts this value to a number, as if by calling `Number(value)` in JS.
+  pub fn to_number<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<'sc, Context>,
+  ) -> Option<Local<'sc, Number>> {
+    unsafe { Local::from_raw(v8__Value__ToNumber(self, &mut *context)) }
+  }
+
+  /// Converts this value to an integer, truncating towards zero.
+  pub fn to_integer<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<'sc, Context>,
+  ) -> Option<Local<'sc, Integer>> {
+    unsafe { Local::from_raw(v8__Value__ToInteger(self, &mut *context)) }
+  }
+
+  /// Converts this value to a 32-bit unsigned integer, as if by `value >>> 0`
+  /// in JS.
+  pub fn to_uint32<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<'sc, Context>,
+  ) -> Option<Local<'sc, Uint32>> {
+    unsafe { Local::from_raw(v8__Value__ToUint32(self, &mut *context)) }
+  }
+
+  /// Converts this value to a 32-bit signed integer, as if by `value | 0` in
+  /// JS.
+  pub fn to_int32<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<'sc, Context>,
+  ) -> Option<Local<'sc, Int32>> {
+    unsafe { Local::from_raw(v8__Value__ToInt32(self, &mut *context)) }
+  }
+
+  /// Converts this value to an object, as if by calling `Object(value)` in JS.
+  /// Returns None and throws a TypeError for null and undefined.
+  pub fn to_object<'sc>(
+    &self,
+    _scope: &mut HandleScope<'sc>,
+    mut context: Local<'sc, Context>,
+  ) -> Option<Local<'sc, Object>> {
+    unsafe { Local::from_raw(v8__Value__ToObject(self, &mut *context)) }
+  }
+
+  /// Converts this value to a boolean, as if by calling `Boolean(value)` in
+  /// JS. This conversion can not fail.
+  pub fn to_boolean<'sc>(
+    &self,
+    scope: &mut HandleScope<'sc>,
+  ) -> Local<'sc, Boolean> {
+    unsafe { Local::from_raw(v8__Value__ToBoolean(self, scope.as_mut())) }
+      .unwrap()
+  }
+
+  /// Returns the result of `Boolean(value)` in JS as a Rust bool.
+  pub fn boolean_value(&self, scope: &mut HandleScope) -> bool {
+    unsafe { v8__Value__BooleanValue(self, scope.as_mut()) }
+  }
+
+  /// Returns the result of `Number(value)` in JS as a Rust f64.
+  pub fn number_value(
+    &self,
+    _scope: &mut HandleScope,
+    mut context: Local<Context>,
+  ) -> Option<f64> {
+    let mut out = MaybeUninit::<Maybe<f64>>::uninit();
+    unsafe {
+      v8__Value__NumberValue(self, &mut *context, out.as_mut_ptr());
+      out.assume_init().into()
+    }
+  }
+
+  /// Returns the value of `to_integer()` as a Rust i64.
+  pub fn integer_value(
+    &self,
+    _scope: &mut HandleScope,
+    mut context: Local<Context>,
+  ) -> Option<i64> {
+    let mut out = MaybeUninit::<Maybe<i64>>::uninit();
+    unsafe {
+      v8__Value__IntegerValue(self, &mut *context, out.as_mut_ptr());
+      out.assume_init().into()
+    }
+  }
+
+  /// Returns the value of `to_uint32()` as a Rust u32.
+  pub fn uint32_value(
+    &self,
+    _scope: &mut HandleScope,
+    mut context: Local<Context>,
+  ) -> Option<u32> {
+    let mut out = MaybeUninit::<Maybe<u32>>::uninit();
+    unsafe {
+      v8__Value__Uint32Value(self, &mut *context, out.as_mut_ptr());
+      out.assume_init().into()
+    }
+  }
+
+  /// Returns the value of `to_int32()` as a Rust i32.
+  pub fn int32_value(
+    &self,
+    _scope: &mut HandleScope,
+    mut context: Local<Context>,
+  ) -> Option<i32> {
+    let mut out = MaybeUninit::<Maybe<i32>>::uninit();
+    unsafe {
+      v8__Value__Int32Value(self, &mut *context, out.as_mut_ptr());
+      out.assume_init().into()
+    }
+  }
+
+  /// Abstract equality comparison, the `==` operator in JS. Returns None if
+  /// an exception was thrown while converting either operand.
+  pub fn equals(
+    &self,
+    mut context: Local<Context>,
+    mut that: Local<Value>,
+  ) -> Option<bool> {
+    unsafe { v8__Value__Equals(self, &mut *context, &mut *that) }.into()
+  }
+
+  /// Strict equality comparison, the `===` operator in JS.
+  pub fn strict_equals(&self, mut that: Local<Value>) -> bool {
+    unsafe { v8__Value__StrictEquals(self, &mut *that) }
+  }
+
+  /// The SameValue algorithm (ECMA-262 7.2.10), as used by `Object.is()` in
+  /// JS. Unlike `strict_equals()`, NaN is equal to itself and +0 and -0 are
+  /// not equal.
+  pub fn same_value(&self, mut that: Local<Value>) -> bool {
+    unsafe { v8__Value__SameValue(self, &mut *that) }
+  }
+
+  /// Returns the result of the `typeof` operator in JS.
+  pub fn type_of<'sc>(
+    &self,
+    scope: &mut HandleScope<'sc>,
+  ) -> Local<'sc, String> {
+    unsafe { Local::from_raw(v8__Value__TypeOf(self, scope.as_mut())) }.unwrap()
+  }
+}
+
+/// The error returned when a checked downcast of a `Local<Value>` fails
+/// because the value is not of the requested type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryFromTypeError {
+  expected_type: &'static str,
 }
+
+impl TryFromTypeError {
+  fn new(expected_type: &'static str) -> Self {
+    Self { expected_type }
+  }
+
+  /// The name of the type the value was expected to be.
+  pub fn expected_type(&self) -> &'static str {
+    self.expected_type
+  }
+}
+
+impl fmt::Display for TryFromTypeError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "Expected {}", self.expected_type)
+  }
+}
+
+impl Error for TryFromTypeError {}
+
+macro_rules! impl_try_from_value {
+  ($type:ident, |$value:ident| $check:expr) => {
+    impl<'sc> TryFrom<Local<'sc, Value>> for Local<'sc, $type> {
+      type Error = TryFromTypeError;
+
+      fn try_from($value: Local<'sc, Value>) -> Result<Self, Self::Error> {
+        if $check {
+          let ptr = &*$value as *const Value as *mut $type;
+          Ok(unsafe { Local::from_raw(ptr) }.unwrap())
+        } else {
+          Err(TryFromTypeError::new(stringify!($type)))
+        }
+      }
+    }
+  };
+}
+
+impl_try_from_value!(Object, |value| value.is_object());
+impl_try_from_value!(Array, |value| value.is_array());
+impl_try_from_value!(Function, |value| value.is_function());
+impl_try_from_value!(External, |value| value.is_external());
+impl_try_from_value!(Primitive, |value| !value.is_object());
+impl_try_from_value!(Boolean, |value| value.is_boolean());
+impl_try_from_value!(Name, |value| value.is_name());
+impl_try_from_value!(String, |value| value.is_string());
+impl_try_from_value!(Number, |value| value.is_number());
+impl_try_from_value!(Integer, |value| value.is_int32() || value.is_uint32());
+impl_try_from_value!(Int32, |value| value.is_int32());
+impl_try_from_value!(Uint32, |value| value.is_uint32());
+impl_try_from_value!(Promise, |value| value.is_promise());
+impl_try_from_value!(ArrayBuffer, |value| value.is_array_buffer());
+impl_try_from_value!(ArrayBufferView, |value| value.is_array_buffer_view());
+impl_try_from_value!(DataView, |value| value.is_data_view());
+impl_try_from_value!(TypedArray, |value| value.is_typed_array());
+impl_try_from_value!(Uint8Array, |value| value.is_uint8_array());
+impl_try_from_value!(Int32Array, |value| value.is_int32_array());
+impl_try_from_value!(Float64Array, |value| value.is_float64_array());
+impl_try_from_value!(BigInt64Array, |value| value.is_big_int64_array());
+impl_try_from_value!(SharedArrayBuffer, |value| value.is_shared_array_buffer());
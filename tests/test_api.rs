@@ -5,6 +5,7 @@ extern crate lazy_static;
 
 use rusty_v8 as v8;
 use rusty_v8::{new_null, FunctionCallbackInfo, HandleScope, Local};
+use std::convert::TryInto;
 use std::default::Default;
 use std::sync::Mutex;
 
@@ -32,6 +33,11 @@ fn setup() -> TestGuard {
   let mut g = INIT_LOCK.lock().unwrap();
   *g += 1;
   if *g == 1 {
+    // Flags are process-wide, so they are set once, before V8 is initialized.
+    v8::V8::set_flags_from_command_line(vec![
+      "binaryname".to_string(),
+      "--expose-gc".to_string(),
+    ]);
     v8::V8::initialize_platform(v8::platform::new_default_platform());
     v8::V8::initialize();
   }
@@ -55,6 +61,40 @@ fn handle_scope_nested() {
   drop(g);
 }
 
+#[test]
+fn isolate_scope() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let mut isolate = v8::Isolate::new(params);
+  assert!(v8::Isolate::get_current().is_none());
+  assert!(!isolate.is_in_use());
+  {
+    let scope1 = v8::IsolateScope::new(&mut isolate);
+    let current = v8::Isolate::get_current().unwrap();
+    assert!(std::ptr::eq(current.as_ptr(), &*scope1));
+    assert!(scope1.is_in_use());
+  }
+  assert!(v8::Isolate::get_current().is_none());
+  assert!(!isolate.is_in_use());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "still entered")]
+fn isolate_dropped_while_entered() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let mut isolate = v8::Isolate::new(params);
+  std::mem::forget(v8::IsolateScope::new(&mut isolate));
+  drop(isolate);
+}
+
 #[test]
 #[allow(clippy::float_cmp)]
 fn handle_scope_numbers() {
@@ -81,6 +121,57 @@ fn handle_scope_numbers() {
   drop(g);
 }
 
+#[test]
+#[allow(clippy::float_cmp)]
+fn escapable_handle_scope() {
+  fn new_number<'p>(
+    scope: &mut HandleScope<'p>,
+    value: f64,
+  ) -> Local<'p, v8::Number> {
+    v8::EscapableHandleScope::enter(scope, |scope| {
+      let number = v8::Number::new(scope, value);
+      scope.escape(number)
+    })
+  }
+
+  let g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let n1 = new_number(scope, 1.5);
+    let n2 = new_number(scope, 2.5);
+    assert_eq!(n1.value(), 1.5);
+    assert_eq!(n2.value(), 2.5);
+  });
+  drop(locker);
+  drop(g);
+}
+
+#[test]
+#[should_panic(expected = "called twice")]
+fn escapable_handle_scope_escape_twice() {
+  let g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    v8::EscapableHandleScope::enter(scope, |scope| {
+      let number = v8::Number::new(scope, 1.0);
+      scope.escape(number);
+      scope.escape(number);
+    });
+  });
+  drop(locker);
+  drop(g);
+}
+
 #[test]
 fn test_string() {
   setup();
@@ -108,17 +199,24 @@ fn v8_str<'sc>(
   v8::String::new(scope, s, v8::NewStringType::Normal).unwrap()
 }
 
+fn eval<'sc>(
+  scope: &mut v8::ContextScope<'_, 'sc>,
+  code: &str,
+) -> Local<'sc, v8::Value> {
+  let source = v8_str(scope, code);
+  let mut script = v8::Script::compile(scope, source, None).unwrap();
+  script.run(scope).unwrap()
+}
+
 #[test]
 fn try_catch() {
   fn eval<'sc>(
-    scope: &mut HandleScope<'sc>,
-    context: Local<v8::Context>,
+    scope: &mut v8::ContextScope<'_, 'sc>,
     code: &'static str,
   ) -> Option<Local<'sc, v8::Value>> {
     let source = v8_str(scope, code);
-    let mut script =
-      v8::Script::compile(&mut *scope, context, source, None).unwrap();
-    script.run(scope, context)
+    let mut script = v8::Script::compile(scope, source, None).unwrap();
+    script.run(scope)
   };
 
   let _g = setup();
@@ -129,13 +227,13 @@ fn try_catch() {
   let isolate = v8::Isolate::new(params);
   let _locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |scope| {
-    let mut context = v8::Context::new(scope);
-    context.enter();
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
     {
       // Error thrown - should be caught.
       let mut try_catch = v8::TryCatch::new(scope);
       let tc = try_catch.enter();
-      let result = eval(scope, context, "throw new Error('foo')");
+      let result = eval(scope, "throw new Error('foo')");
       assert!(result.is_none());
       assert!(tc.has_caught());
       assert!(tc.exception().is_some());
@@ -150,7 +248,7 @@ fn try_catch() {
       // No error thrown.
       let mut try_catch = v8::TryCatch::new(scope);
       let tc = try_catch.enter();
-      let result = eval(scope, context, "1 + 1");
+      let result = eval(scope, "1 + 1");
       assert!(result.is_some());
       assert!(!tc.has_caught());
       assert!(tc.exception().is_none());
@@ -165,7 +263,7 @@ fn try_catch() {
       {
         let mut try_catch_2 = v8::TryCatch::new(scope);
         let tc2 = try_catch_2.enter();
-        eval(scope, context, "throw 'bar'");
+        eval(scope, "throw 'bar'");
         assert!(tc2.has_caught());
         assert!(tc2.rethrow().is_some());
         tc2.reset();
@@ -173,10 +271,330 @@ fn try_catch() {
       }
       assert!(tc1.has_caught());
     };
-    context.exit();
   });
 }
 
+#[test]
+fn create_params_constraints() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let constraints = params.constraints();
+  constraints.set_initial_old_generation_size_in_bytes(1 << 20);
+  constraints.set_max_old_generation_size_in_bytes(64 << 20);
+  constraints.set_initial_young_generation_size_in_bytes(1 << 20);
+  constraints.set_max_young_generation_size_in_bytes(4 << 20);
+  assert_eq!(constraints.initial_old_generation_size_in_bytes(), 1 << 20);
+  assert_eq!(constraints.max_old_generation_size_in_bytes(), 64 << 20);
+  assert_eq!(
+    constraints.initial_young_generation_size_in_bytes(),
+    1 << 20
+  );
+  assert_eq!(constraints.max_young_generation_size_in_bytes(), 4 << 20);
+
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  static HISTOGRAM_COUNT: AtomicUsize = AtomicUsize::new(0);
+  extern "C" fn create_histogram(
+    _name: *const std::os::raw::c_char,
+    _min: i32,
+    _max: i32,
+    _buckets: usize,
+  ) -> *mut std::ffi::c_void {
+    HISTOGRAM_COUNT.fetch_add(1, Ordering::SeqCst);
+    std::ptr::null_mut()
+  }
+  extern "C" fn add_histogram_sample(
+    _histogram: *mut std::ffi::c_void,
+    _sample: i32,
+  ) {
+    unreachable!();
+  }
+  params.set_create_histogram_callback(create_histogram);
+  params.set_add_histogram_sample_callback(add_histogram_sample);
+
+  let isolate = v8::Isolate::new(params);
+  assert!(HISTOGRAM_COUNT.load(Ordering::SeqCst) > 0);
+  drop(isolate);
+}
+
+#[test]
+fn create_params_counter_lookup() {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  static LOOKUP_COUNT: AtomicUsize = AtomicUsize::new(0);
+  extern "C" fn counter_lookup(name: *const std::os::raw::c_char) -> *mut i32 {
+    assert!(!name.is_null());
+    LOOKUP_COUNT.fetch_add(1, Ordering::SeqCst);
+    std::ptr::null_mut()
+  }
+  static EXTERNAL_REFERENCES: [isize; 2] = [1, 0];
+
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  params.set_counter_lookup_callback(counter_lookup);
+  // Only used when deserializing a snapshot, which the default one doesn't
+  // need, but the isolate must still be created successfully.
+  params.set_external_references(&EXTERNAL_REFERENCES);
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let source = v8_str(scope, "[1, 2, 3].map(x => x * 2).join()");
+    let mut script = v8::Script::compile(scope, source, None).unwrap();
+    let result = script.run(scope).unwrap();
+    assert!(result.strict_equals(v8_str(scope, "2,4,6").into()));
+  });
+  drop(locker);
+  assert!(LOOKUP_COUNT.load(Ordering::SeqCst) > 0);
+}
+
+#[test]
+#[should_panic]
+fn create_params_external_references_unterminated() {
+  static EXTERNAL_REFERENCES: [isize; 1] = [1];
+  let mut params = v8::Isolate::create_params();
+  params.set_external_references(&EXTERNAL_REFERENCES);
+}
+
+#[test]
+fn create_params_allow_atomics_wait() {
+  let _g = setup();
+  for allow in &[true, false] {
+    let mut params = v8::Isolate::create_params();
+    params.set_array_buffer_allocator(
+      v8::array_buffer::Allocator::new_default_allocator(),
+    );
+    params.set_allow_atomics_wait(*allow);
+    let isolate = v8::Isolate::new(params);
+    let locker = v8::Locker::new(&isolate);
+    v8::HandleScope::enter(&isolate, |scope| {
+      let context = v8::Context::new(scope);
+      let scope = &mut v8::ContextScope::new(scope, context);
+      let source = v8_str(
+        scope,
+        "Atomics.wait(new Int32Array(new SharedArrayBuffer(4)), 0, 0, 0)",
+      );
+      let mut script = v8::Script::compile(scope, source, None).unwrap();
+      let mut try_catch = v8::TryCatch::new(scope);
+      let tc = try_catch.enter();
+      let result = script.run(scope);
+      assert_eq!(result.is_some(), *allow);
+      assert_eq!(tc.has_caught(), !*allow);
+    });
+    drop(locker);
+  }
+}
+
+#[test]
+fn array_buffer_allocator_impl() {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use v8::array_buffer::{AllocatorBase, AllocatorImpl, AsAllocator};
+
+  static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+  static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+  struct CountingAllocator {
+    base: AllocatorBase,
+  }
+
+  impl AllocatorImpl for CountingAllocator {
+    fn base(&self) -> &AllocatorBase {
+      &self.base
+    }
+    fn base_mut(&mut self) -> &mut AllocatorBase {
+      &mut self.base
+    }
+    fn allocate(&self, length: usize) -> *mut std::ffi::c_void {
+      ALLOCATED.fetch_add(length, Ordering::SeqCst);
+      Box::into_raw(vec![0u8; length].into_boxed_slice()) as *mut _
+    }
+    fn allocate_uninitialized(&self, length: usize) -> *mut std::ffi::c_void {
+      self.allocate(length)
+    }
+    fn free(&self, data: *mut std::ffi::c_void, length: usize) {
+      ALLOCATED.fetch_sub(length, Ordering::SeqCst);
+      drop(unsafe { Vec::from_raw_parts(data as *mut u8, length, length) });
+    }
+  }
+
+  impl Drop for CountingAllocator {
+    fn drop(&mut self) {
+      DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+  }
+
+  let _g = setup();
+  let allocator = Box::new(CountingAllocator {
+    base: AllocatorBase::new::<CountingAllocator>(),
+  });
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(allocator.into_unique_ref());
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let source = v8_str(scope, "new ArrayBuffer(1024)");
+    let mut script = v8::Script::compile(scope, source, None).unwrap();
+    script.run(scope).unwrap();
+    assert!(ALLOCATED.load(Ordering::SeqCst) >= 1024);
+  });
+  drop(locker);
+  drop(isolate);
+  assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn array_buffer() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let mut ab = v8::ArrayBuffer::new(scope, 42);
+    assert_eq!(42, ab.byte_length());
+    let bs = ab.get_backing_store();
+    assert_eq!(42, bs.byte_length());
+    assert!(!bs.is_shared());
+    assert_eq!(2, bs.use_count());
+    assert!(ab.is_detachable());
+    ab.detach();
+    assert_eq!(0, ab.byte_length());
+
+    let bs = v8::ArrayBuffer::new_backing_store(scope, 84);
+    assert_eq!(84, bs.byte_length());
+    assert_eq!(1, bs.use_count());
+    let ab = v8::ArrayBuffer::new_with_backing_store(scope, &bs);
+    assert_eq!(84, ab.byte_length());
+    assert_eq!(2, bs.use_count());
+
+    let data: Box<[u8]> = vec![0, 1, 2, 3, 4].into_boxed_slice();
+    let bs = v8::ArrayBuffer::new_backing_store_from_boxed_slice(data);
+    let bs2 = bs.clone();
+    assert_eq!(2, bs.use_count());
+    drop(bs2);
+    assert_eq!(1, bs.use_count());
+    let mut ab = v8::ArrayBuffer::new_with_backing_store(scope, &bs);
+    drop(bs);
+    let bs = ab.get_backing_store();
+    assert_eq!(5, ab.byte_length());
+    let bytes =
+      unsafe { std::slice::from_raw_parts(bs.data() as *const u8, 5) };
+    assert_eq!(bytes, &[0, 1, 2, 3, 4]);
+
+    let bs = v8::ArrayBuffer::new_backing_store_from_vec(vec![]);
+    assert_eq!(0, bs.byte_length());
+  });
+  drop(locker);
+}
+
+#[test]
+fn shared_array_buffer() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let mut sab = v8::SharedArrayBuffer::new(scope, 16);
+    assert_eq!(16, sab.byte_length());
+    let bs = sab.get_backing_store();
+    assert_eq!(16, bs.byte_length());
+    assert!(bs.is_shared());
+
+    let data: Box<[u8]> = vec![5, 6, 7].into_boxed_slice();
+    let bs = v8::SharedArrayBuffer::new_backing_store_from_boxed_slice(data);
+    let sab = v8::SharedArrayBuffer::new_with_backing_store(scope, &bs);
+    assert_eq!(3, sab.byte_length());
+    assert!(bs.is_shared());
+
+    let mut data = Vec::with_capacity(10);
+    data.extend_from_slice(&[8, 9]);
+    let bs = v8::SharedArrayBuffer::new_backing_store_from_vec(data);
+    assert_eq!(2, bs.byte_length());
+    assert!(bs.is_shared());
+  });
+  drop(locker);
+}
+
+#[test]
+fn typed_arrays() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let ab = v8::ArrayBuffer::new(scope, 16);
+    let u8a = v8::Uint8Array::new(scope, ab, 2, 4).unwrap();
+    assert_eq!(u8a.length(), 4);
+    assert_eq!(u8a.byte_offset(), 2);
+    assert_eq!(u8a.byte_length(), 4);
+    u8a
+      .with_mut_slice(scope, |bytes| bytes.copy_from_slice(&[1, 2, 3, 4]))
+      .unwrap();
+    assert_eq!(
+      u8a.with_slice(scope, |bytes| bytes.to_vec()).unwrap(),
+      [1, 2, 3, 4]
+    );
+    let buffer = u8a.buffer(scope).unwrap();
+    assert_eq!(buffer.byte_length(), 16);
+
+    let dv = v8::DataView::new(scope, ab, 0, 8).unwrap();
+    assert_eq!(
+      dv.with_slice(scope, |bytes| bytes.to_vec()).unwrap(),
+      [0, 0, 1, 2, 3, 4, 0, 0]
+    );
+    let mut copy = [0u8; 3];
+    assert_eq!(dv.copy_contents(&mut copy), 3);
+    assert_eq!(copy, [0, 0, 1]);
+
+    let i32a = v8::Int32Array::new(scope, ab, 4, 3).unwrap();
+    assert_eq!(i32a.byte_length(), 12);
+    let f64a = v8::Float64Array::new(scope, ab, 8, 1).unwrap();
+    assert_eq!(f64a.length(), 1);
+    let bi64a = v8::BigInt64Array::new(scope, ab, 0, 2).unwrap();
+    assert_eq!(bi64a.byte_length(), 16);
+
+    // Misaligned or out of bounds.
+    assert!(v8::Int32Array::new(scope, ab, 2, 1).is_none());
+    assert!(v8::Float64Array::new(scope, ab, 8, 2).is_none());
+    assert!(v8::Uint8Array::new(scope, ab, 17, 0).is_none());
+    assert!(v8::DataView::new(scope, ab, 10, 7).is_none());
+
+    let source = v8_str(scope, "new Uint8Array([5, 6, 7])");
+    let mut script = v8::Script::compile(scope, source, None).unwrap();
+    let u8a: Local<v8::Uint8Array> =
+      script.run(scope).unwrap().try_into().unwrap();
+    assert_eq!(
+      u8a.with_slice(scope, |bytes| bytes.to_vec()).unwrap(),
+      [5, 6, 7]
+    );
+  });
+  drop(locker);
+}
+
 #[test]
 fn isolate_add_message_listener() {
   let g = setup();
@@ -205,13 +623,12 @@ fn isolate_add_message_listener() {
 
   let locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |s| {
-    let mut context = v8::Context::new(s);
-    context.enter();
+    let context = v8::Context::new(s);
+    let s = &mut v8::ContextScope::new(s, context);
     let source = v8::String::new(s, "throw 'foo'", Default::default()).unwrap();
-    let mut script = v8::Script::compile(s, context, source, None).unwrap();
-    assert!(script.run(s, context).is_none());
+    let mut script = v8::Script::compile(s, source, None).unwrap();
+    assert!(script.run(s).is_none());
     assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
-    context.exit();
   });
   drop(locker);
   drop(g);
@@ -228,78 +645,374 @@ fn script_compile_and_run() {
   let locker = v8::Locker::new(&isolate);
 
   v8::HandleScope::enter(&isolate, |s| {
-    let mut context = v8::Context::new(s);
-    context.enter();
+    let context = v8::Context::new(s);
+    let s = &mut v8::ContextScope::new(s, context);
     let source =
       v8::String::new(s, "'Hello ' + 13 + 'th planet'", Default::default())
         .unwrap();
-    let mut script = v8::Script::compile(s, context, source, None).unwrap();
+    let mut script = v8::Script::compile(s, source, None).unwrap();
     source.to_rust_string_lossy(s);
-    let result = script.run(s, context).unwrap();
-    // TODO: safer casts.
-    let result: v8::Local<v8::String> =
-      unsafe { std::mem::transmute_copy(&result) };
+    let result = script.run(s).unwrap();
+    let result: v8::Local<v8::String> = result.try_into().unwrap();
     assert_eq!(result.to_rust_string_lossy(s), "Hello 13th planet");
-    context.exit();
   });
   drop(locker);
 }
 
 #[test]
-fn script_origin() {
-  setup();
+fn context_scope_nested() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context1 = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context1);
+    eval(scope, "var x = 'outer'");
+    {
+      let context2 = v8::Context::new(scope);
+      let scope = &mut v8::ContextScope::new(scope, context2);
+      let result: Local<v8::String> =
+        eval(scope, "typeof x").try_into().unwrap();
+      assert_eq!(result.to_rust_string_lossy(scope), "undefined");
+    }
+    let result: Local<v8::String> = eval(scope, "x").try_into().unwrap();
+    assert_eq!(result.to_rust_string_lossy(scope), "outer");
+  });
+  drop(locker);
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn value_checker() {
+  let _g = setup();
   let mut params = v8::Isolate::create_params();
   params.set_array_buffer_allocator(
     v8::array_buffer::Allocator::new_default_allocator(),
   );
   let isolate = v8::Isolate::new(params);
   let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
 
-  v8::HandleScope::enter(&isolate, |s| {
-    let mut context = v8::Context::new(s);
-    context.enter();
+    let value = eval(scope, "undefined");
+    assert!(value.is_undefined());
+    assert!(value.is_null_or_undefined());
+    assert!(!value.is_object());
 
-    let resource_name =
-      v8::String::new(s, "foo.js", Default::default()).unwrap();
-    let resource_line_offset = v8::Integer::new(s, 4);
-    let resource_column_offset = v8::Integer::new(s, 5);
-    let resource_is_shared_cross_origin = v8::new_true(s);
-    let script_id = v8::Integer::new(s, 123);
-    let source_map_url =
-      v8::String::new(s, "source_map_url", Default::default()).unwrap();
-    let resource_is_opaque = v8::new_true(s);
-    let is_wasm = v8::new_false(s);
-    let is_module = v8::new_false(s);
+    let value = eval(scope, "true");
+    assert!(value.is_true());
+    assert!(!value.is_false());
+    assert!(value.is_boolean());
+    let _: Local<v8::Boolean> = value.try_into().unwrap();
+    let _: Local<v8::Primitive> = value.try_into().unwrap();
 
-    let script_origin = v8::ScriptOrigin::new(
-      resource_name.into(),
-      resource_line_offset,
-      resource_column_offset,
-      resource_is_shared_cross_origin,
-      script_id,
-      source_map_url.into(),
-      resource_is_opaque,
-      is_wasm,
-      is_module,
+    let value = eval(scope, "'hello'");
+    assert!(value.is_string());
+    assert!(value.is_name());
+    assert!(!value.is_symbol());
+    let _: Local<v8::String> = value.try_into().unwrap();
+    let _: Local<v8::Name> = value.try_into().unwrap();
+
+    let value = eval(scope, "Symbol()");
+    assert!(value.is_symbol());
+    assert!(value.is_name());
+    assert!(!value.is_string());
+
+    let value = eval(scope, "-42");
+    assert!(value.is_number());
+    assert!(value.is_int32());
+    assert!(!value.is_uint32());
+    let _: Local<v8::Number> = value.try_into().unwrap();
+    let integer: Local<v8::Integer> = value.try_into().unwrap();
+    let number: Local<v8::Number> = integer.into();
+    let primitive: Local<v8::Primitive> = integer.into();
+    assert_eq!(number.value(), -42.0);
+    assert!(primitive.is_int32());
+
+    let value = eval(scope, "1.5");
+    assert!(value.is_number());
+    assert!(!value.is_int32());
+    let result: Result<Local<v8::Integer>, _> = value.try_into();
+    assert!(result.is_err());
+
+    let value = eval(scope, "10n");
+    assert!(value.is_big_int());
+    assert!(!value.is_number());
+
+    let value = eval(scope, "({})");
+    assert!(value.is_object());
+    assert!(!value.is_array());
+    assert!(!value.is_function());
+    let _: Local<v8::Object> = value.try_into().unwrap();
+
+    let value = eval(scope, "[1, 2]");
+    assert!(value.is_array());
+    assert!(value.is_object());
+
+    let value = eval(scope, "(function() {})");
+    assert!(value.is_function());
+    assert!(value.is_object());
+    let _: Local<v8::Function> = value.try_into().unwrap();
+
+    let value = eval(scope, "(async function() {})");
+    assert!(value.is_async_function());
+    let value = eval(scope, "(function*() {})");
+    assert!(value.is_generator_function());
+    let value = eval(scope, "(function*() {})()");
+    assert!(value.is_generator_object());
+
+    let value = eval(scope, "Promise.resolve()");
+    assert!(value.is_promise());
+    let _: Local<v8::Promise> = value.try_into().unwrap();
+
+    assert!(eval(scope, "new Date()").is_date());
+    assert!(eval(scope, "/x/").is_reg_exp());
+    assert!(eval(scope, "new Error()").is_native_error());
+    assert!(
+      eval(scope, "(function() { return arguments; })()").is_arguments_object()
     );
+    assert!(eval(scope, "new Map()").is_map());
+    assert!(eval(scope, "new Set()").is_set());
+    assert!(eval(scope, "new Map().keys()").is_map_iterator());
+    assert!(eval(scope, "new Set().values()").is_set_iterator());
+    assert!(eval(scope, "new WeakMap()").is_weak_map());
+    assert!(eval(scope, "new WeakSet()").is_weak_set());
+    assert!(eval(scope, "new Proxy({}, {})").is_proxy());
+    assert!(eval(scope, "new Boolean(true)").is_boolean_object());
+    assert!(eval(scope, "new Number(1)").is_number_object());
+    assert!(eval(scope, "new String('')").is_string_object());
+    assert!(eval(scope, "Object(Symbol())").is_symbol_object());
+    assert!(eval(scope, "Object(1n)").is_big_int_object());
 
-    let source = v8::String::new(s, "1+2", Default::default()).unwrap();
-    let mut script =
-      v8::Script::compile(s, context, source, Some(&script_origin)).unwrap();
-    source.to_rust_string_lossy(s);
-    let _result = script.run(s, context).unwrap();
-    context.exit();
+    let value = eval(scope, "new ArrayBuffer(8)");
+    assert!(value.is_array_buffer());
+    assert!(!value.is_shared_array_buffer());
+    let _: Local<v8::ArrayBuffer> = value.try_into().unwrap();
+
+    let value = eval(scope, "new Float64Array(1)");
+    assert!(value.is_array_buffer_view());
+    assert!(value.is_typed_array());
+    assert!(value.is_float64_array());
+    assert!(!value.is_uint8_array());
+    let _: Local<v8::ArrayBufferView> = value.try_into().unwrap();
+    let _: Local<v8::TypedArray> = value.try_into().unwrap();
+    let _: Local<v8::Float64Array> = value.try_into().unwrap();
+    let result: Result<Local<v8::Uint8Array>, _> = value.try_into();
+    assert!(result.is_err());
+
+    let value = eval(scope, "new DataView(new ArrayBuffer(8))");
+    assert!(value.is_data_view());
+    assert!(value.is_array_buffer_view());
+    assert!(!value.is_typed_array());
+    let _: Local<v8::DataView> = value.try_into().unwrap();
   });
   drop(locker);
 }
 
 #[test]
-fn get_version() {
-  assert!(v8::V8::get_version().len() > 3);
+fn try_from_value_error() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let value: Local<v8::Value> = v8::new_null(scope).into();
+    let result: Result<Local<v8::Object>, _> = value.try_into();
+    let err = result.err().unwrap();
+    assert_eq!(err.expected_type(), "Object");
+    assert_eq!(err.to_string(), "Expected Object");
+  });
+  drop(locker);
 }
 
 #[test]
-fn set_flags_from_command_line() {
+#[allow(clippy::float_cmp)]
+fn value_conversions() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let value = eval(scope, "'-3.75'");
+    let number = value.to_number(scope, context).unwrap();
+    assert_eq!(number.value(), -3.75);
+    let integer = value.to_integer(scope, context).unwrap();
+    assert_eq!(integer.value(), -3);
+    let int32 = value.to_int32(scope, context).unwrap();
+    assert_eq!(int32.value(), -3);
+    let uint32 = value.to_uint32(scope, context).unwrap();
+    assert_eq!(uint32.value(), 4_294_967_293);
+    assert_eq!(value.number_value(scope, context), Some(-3.75));
+    assert_eq!(value.integer_value(scope, context), Some(-3));
+    assert_eq!(value.int32_value(scope, context), Some(-3));
+    assert_eq!(value.uint32_value(scope, context), Some(4_294_967_293));
+    assert!(value.boolean_value(scope));
+    assert!(value.to_boolean(scope).is_true());
+
+    let value = eval(scope, "({ toString() { return 'custom'; } })");
+    let string = value.to_string(scope, context).unwrap();
+    assert_eq!(string.to_rust_string_lossy(scope), "custom");
+    let string = value.to_detail_string(scope, context).unwrap();
+    assert_eq!(string.to_rust_string_lossy(scope), "custom");
+
+    let value = eval(scope, "''");
+    assert!(!value.boolean_value(scope));
+    assert!(value.to_boolean(scope).is_false());
+    assert_eq!(value.number_value(scope, context), Some(0.0));
+    let object = value.to_object(scope, context).unwrap();
+    assert!(object.is_string_object());
+
+    let value = eval(scope, "({ valueOf() { throw new Error(); } })");
+    {
+      let mut try_catch = v8::TryCatch::new(scope);
+      let tc = try_catch.enter();
+      assert!(value.to_number(scope, context).is_none());
+      assert!(value.number_value(scope, context).is_none());
+      assert!(value.int32_value(scope, context).is_none());
+      assert!(tc.has_caught());
+    }
+
+    let value: Local<v8::Value> = v8::new_undefined(scope).into();
+    assert!(value.number_value(scope, context).unwrap().is_nan());
+    {
+      let mut try_catch = v8::TryCatch::new(scope);
+      let tc = try_catch.enter();
+      assert!(value.to_object(scope, context).is_none());
+      assert!(tc.has_caught());
+    }
+  });
+  drop(locker);
+}
+
+#[test]
+fn value_equality() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let one: Local<v8::Value> = v8::Integer::new(scope, 1).into();
+    let one_str: Local<v8::Value> = v8_str(scope, "1").into();
+    assert!(one.strict_equals(one));
+    assert!(!one.strict_equals(one_str));
+    assert_eq!(one.equals(context, one_str), Some(true));
+    assert!(one.same_value(one));
+
+    let nan = eval(scope, "NaN");
+    assert!(!nan.strict_equals(nan));
+    assert!(nan.same_value(nan));
+
+    let zero = eval(scope, "0");
+    let minus_zero = eval(scope, "-0");
+    assert!(zero.strict_equals(minus_zero));
+    assert!(!zero.same_value(minus_zero));
+
+    let null: Local<v8::Value> = v8::new_null(scope).into();
+    let undefined: Local<v8::Value> = v8::new_undefined(scope).into();
+    assert_eq!(null.equals(context, undefined), Some(true));
+    assert!(!null.strict_equals(undefined));
+
+    let object = eval(scope, "({ valueOf() { throw new Error(); } })");
+    {
+      let mut try_catch = v8::TryCatch::new(scope);
+      let tc = try_catch.enter();
+      assert_eq!(object.equals(context, one), None);
+      assert!(tc.has_caught());
+    }
+
+    for (code, expected) in &[
+      ("undefined", "undefined"),
+      ("null", "object"),
+      ("true", "boolean"),
+      ("1", "number"),
+      ("1n", "bigint"),
+      ("'foo'", "string"),
+      ("Symbol()", "symbol"),
+      ("({})", "object"),
+      ("(function() {})", "function"),
+    ] {
+      let value = eval(scope, code);
+      let type_of = value.type_of(scope);
+      assert_eq!(type_of.to_rust_string_lossy(scope), *expected);
+    }
+  });
+  drop(locker);
+}
+
+#[test]
+fn script_origin() {
+  setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+
+  v8::HandleScope::enter(&isolate, |s| {
+    let context = v8::Context::new(s);
+    let s = &mut v8::ContextScope::new(s, context);
+
+    let resource_name =
+      v8::String::new(s, "foo.js", Default::default()).unwrap();
+    let resource_line_offset = v8::Integer::new(s, 4);
+    let resource_column_offset = v8::Integer::new(s, 5);
+    let resource_is_shared_cross_origin = v8::new_true(s);
+    let script_id = v8::Integer::new(s, 123);
+    let source_map_url =
+      v8::String::new(s, "source_map_url", Default::default()).unwrap();
+    let resource_is_opaque = v8::new_true(s);
+    let is_wasm = v8::new_false(s);
+    let is_module = v8::new_false(s);
+
+    let script_origin = v8::ScriptOrigin::new(
+      resource_name.into(),
+      resource_line_offset,
+      resource_column_offset,
+      resource_is_shared_cross_origin,
+      script_id,
+      source_map_url.into(),
+      resource_is_opaque,
+      is_wasm,
+      is_module,
+    );
+
+    let source = v8::String::new(s, "1+2", Default::default()).unwrap();
+    let mut script =
+      v8::Script::compile(s, source, Some(&script_origin)).unwrap();
+    source.to_rust_string_lossy(s);
+    let _result = script.run(s).unwrap();
+  });
+  drop(locker);
+}
+
+#[test]
+fn get_version() {
+  assert!(v8::V8::get_version().len() > 3);
+}
+
+#[test]
+fn set_flags_from_command_line() {
   let r = v8::V8::set_flags_from_command_line(vec![
     "binaryname".to_string(),
     "--log-colour".to_string(),
@@ -381,10 +1094,10 @@ fn exception() {
   );
   let mut isolate = v8::Isolate::new(params);
   let locker = v8::Locker::new(&isolate);
-  isolate.enter();
+  let isolate = v8::IsolateScope::new(&mut isolate);
   v8::HandleScope::enter(&isolate, |scope| {
-    let mut context = v8::Context::new(scope);
-    context.enter();
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
     let reference = "This is a test error";
     let local =
       v8::String::new(scope, reference, v8::NewStringType::Normal).unwrap();
@@ -401,10 +1114,8 @@ fn exception() {
       rust_msg_string
     );
     assert!(v8::get_stack_trace(scope, exception).is_none());
-    context.exit();
   });
   drop(locker);
-  isolate.exit();
 }
 
 #[test]
@@ -417,8 +1128,8 @@ fn json() {
   let isolate = v8::Isolate::new(params);
   let locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |s| {
-    let mut context = v8::Context::new(s);
-    context.enter();
+    let context = v8::Context::new(s);
+    let s = &mut v8::ContextScope::new(s, context);
     let json_string = v8_str(s, "{\"a\": 1, \"b\": 2}");
     let maybe_value = v8::json::parse(context, json_string);
     assert!(maybe_value.is_some());
@@ -428,17 +1139,10 @@ fn json() {
     let stringified = maybe_stringified.unwrap();
     let rust_str = stringified.to_rust_string_lossy(s);
     assert_eq!("{\"a\":1,\"b\":2}".to_string(), rust_str);
-    context.exit();
   });
   drop(locker);
 }
 
-// TODO Safer casts https://github.com/denoland/rusty_v8/issues/51
-fn cast<U, T>(local: v8::Local<T>) -> v8::Local<U> {
-  let cast_local: v8::Local<U> = unsafe { std::mem::transmute_copy(&local) };
-  cast_local
-}
-
 #[test]
 fn object() {
   setup();
@@ -449,27 +1153,27 @@ fn object() {
   let isolate = v8::Isolate::new(params);
   let locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |scope| {
-    let mut context = v8::Context::new(scope);
-    context.enter();
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
     let null: v8::Local<v8::Value> = new_null(scope).into();
     let s1 = v8::String::new(scope, "a", v8::NewStringType::Normal).unwrap();
     let s2 = v8::String::new(scope, "b", v8::NewStringType::Normal).unwrap();
-    let name1: Local<v8::Name> = cast(s1);
-    let name2: Local<v8::Name> = cast(s2);
+    let name1: Local<v8::Name> = s1.into();
+    let name2: Local<v8::Name> = s2.into();
     let names = vec![name1, name2];
     let v1: v8::Local<v8::Value> = v8::Number::new(scope, 1.0).into();
     let v2: v8::Local<v8::Value> = v8::Number::new(scope, 2.0).into();
     let values = vec![v1, v2];
     let object = v8::Object::new(scope, null, names, values, 2);
     assert!(!object.is_null_or_undefined());
-    context.exit();
   });
   drop(locker);
 }
 
 #[test]
-fn promise_resolved() {
-  setup();
+#[allow(clippy::float_cmp)]
+fn object_get_set() {
+  let _g = setup();
   let mut params = v8::Isolate::create_params();
   params.set_array_buffer_allocator(
     v8::array_buffer::Allocator::new_default_allocator(),
@@ -477,39 +1181,79 @@ fn promise_resolved() {
   let isolate = v8::Isolate::new(params);
   let locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |scope| {
-    let mut context = v8::Context::new(scope);
-    context.enter();
-    let maybe_resolver = v8::PromiseResolver::new(scope, context);
-    assert!(maybe_resolver.is_some());
-    let mut resolver = maybe_resolver.unwrap();
-    let mut promise = resolver.get_promise(scope);
-    assert!(!promise.has_handler());
-    assert_eq!(promise.state(), v8::PromiseState::Pending);
-    let str =
-      v8::String::new(scope, "test", v8::NewStringType::Normal).unwrap();
-    let value: Local<v8::Value> = cast(str);
-    resolver.resolve(context, value);
-    assert_eq!(promise.state(), v8::PromiseState::Fulfilled);
-    let result = promise.result(scope);
-    let result_str: v8::Local<v8::String> = cast(result);
-    assert_eq!(result_str.to_rust_string_lossy(scope), "test".to_string());
-    // Resolve again with different value, since promise is already in `Fulfilled` state
-    // it should be ignored.
-    let str =
-      v8::String::new(scope, "test2", v8::NewStringType::Normal).unwrap();
-    let value: Local<v8::Value> = cast(str);
-    resolver.resolve(context, value);
-    let result = promise.result(scope);
-    let result_str: v8::Local<v8::String> = cast(result);
-    assert_eq!(result_str.to_rust_string_lossy(scope), "test".to_string());
-    context.exit();
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let null: Local<v8::Value> = new_null(scope).into();
+    let object = v8::Object::new(scope, null, vec![], vec![], 0);
+
+    let key_a: Local<v8::Name> = v8_str(scope, "a").into();
+    let key_b: Local<v8::Name> = v8_str(scope, "b").into();
+    let key_c: Local<v8::Name> = v8_str(scope, "c").into();
+    let one: Local<v8::Value> = v8::Number::new(scope, 1.0).into();
+    let two: Local<v8::Value> = v8::Number::new(scope, 2.0).into();
+
+    assert_eq!(object.set(context, key_a.into(), one), Some(true));
+    let value = object.get(scope, context, key_a.into()).unwrap();
+    assert_eq!(value.number_value(scope, context), Some(1.0));
+    assert_eq!(object.has(context, key_a.into()), Some(true));
+    assert_eq!(object.has_own_property(context, key_a), Some(true));
+    assert_eq!(object.has(context, key_b.into()), Some(false));
+    let value = object.get(scope, context, key_b.into()).unwrap();
+    assert!(value.is_undefined());
+
+    assert_eq!(object.set_index(context, 3, two), Some(true));
+    let value = object.get_index(scope, context, 3).unwrap();
+    assert_eq!(value.number_value(scope, context), Some(2.0));
+
+    assert_eq!(object.create_data_property(context, key_b, two), Some(true));
+    assert_eq!(
+      object.define_own_property(
+        context,
+        key_c,
+        one,
+        v8::PropertyAttribute::READ_ONLY | v8::PropertyAttribute::DONT_ENUM
+      ),
+      Some(true)
+    );
+    assert_eq!(object.set(context, key_c.into(), two), Some(true));
+    let value = object.get(scope, context, key_c.into()).unwrap();
+    assert_eq!(value.number_value(scope, context), Some(1.0));
+
+    let names = object.get_own_property_names(scope, context).unwrap();
+    assert_eq!(names.length(), 3);
+    let name = names.get_index(scope, context, 0).unwrap();
+    let name = name.to_string(scope, context).unwrap();
+    assert_eq!(name.to_rust_string_lossy(scope), "3");
+    let name = names.get_index(scope, context, 2).unwrap();
+    let name = name.to_string(scope, context).unwrap();
+    assert_eq!(name.to_rust_string_lossy(scope), "b");
+
+    assert_eq!(object.delete(context, key_a.into()), Some(true));
+    assert_eq!(object.has(context, key_a.into()), Some(false));
+
+    let prototype = object.get_prototype(scope).unwrap();
+    assert!(prototype.is_null());
+    let proto = v8::Object::new(scope, null, vec![key_a], vec![one], 1);
+    assert_eq!(object.set_prototype(context, proto.into()), Some(true));
+    let prototype = object.get_prototype(scope).unwrap();
+    assert!(prototype.strict_equals(proto.into()));
+    assert_eq!(object.has(context, key_a.into()), Some(true));
+    assert_eq!(object.has_own_property(context, key_a), Some(false));
+    let names = object.get_property_names(scope, context).unwrap();
+    assert_eq!(names.length(), 3);
+
+    let array = v8::Array::new(scope, 2);
+    assert_eq!(array.length(), 2);
+    assert!(array.is_array());
+    assert_eq!(array.set_index(context, 2, one), Some(true));
+    assert_eq!(array.length(), 3);
   });
   drop(locker);
 }
 
 #[test]
-fn promise_rejected() {
-  setup();
+fn object_internal_fields() {
+  let _g = setup();
   let mut params = v8::Isolate::create_params();
   params.set_array_buffer_allocator(
     v8::array_buffer::Allocator::new_default_allocator(),
@@ -517,55 +1261,81 @@ fn promise_rejected() {
   let isolate = v8::Isolate::new(params);
   let locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |scope| {
-    let mut context = v8::Context::new(scope);
-    context.enter();
-    let maybe_resolver = v8::PromiseResolver::new(scope, context);
-    assert!(maybe_resolver.is_some());
-    let mut resolver = maybe_resolver.unwrap();
-    let mut promise = resolver.get_promise(scope);
-    assert!(!promise.has_handler());
-    assert_eq!(promise.state(), v8::PromiseState::Pending);
-    let str =
-      v8::String::new(scope, "test", v8::NewStringType::Normal).unwrap();
-    let value: Local<v8::Value> = cast(str);
-    let rejected = resolver.reject(context, value);
-    assert!(rejected.unwrap());
-    assert_eq!(promise.state(), v8::PromiseState::Rejected);
-    let result = promise.result(scope);
-    let result_str: v8::Local<v8::String> = cast(result);
-    assert_eq!(result_str.to_rust_string_lossy(scope), "test".to_string());
-    // Reject again with different value, since promise is already in `Rejected` state
-    // it should be ignored.
-    let str =
-      v8::String::new(scope, "test2", v8::NewStringType::Normal).unwrap();
-    let value: Local<v8::Value> = cast(str);
-    resolver.reject(context, value);
-    let result = promise.result(scope);
-    let result_str: v8::Local<v8::String> = cast(result);
-    assert_eq!(result_str.to_rust_string_lossy(scope), "test".to_string());
-    context.exit();
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let template = v8::ObjectTemplate::new(scope);
+    assert_eq!(template.internal_field_count(), 0);
+    template.set_internal_field_count(2);
+    assert_eq!(template.internal_field_count(), 2);
+
+    let object = template.new_instance(scope, context).unwrap();
+    assert_eq!(object.internal_field_count(), 2);
+    let field = object.get_internal_field(scope, 0);
+    assert!(field.is_undefined());
+
+    let value: Local<v8::Value> = v8_str(scope, "native").into();
+    object.set_internal_field(0, value);
+    let field = object.get_internal_field(scope, 0);
+    assert!(field.strict_equals(value));
+
+    let mut data = Box::new(42u64);
+    let ptr = &mut *data as *mut u64 as *mut std::ffi::c_void;
+    unsafe { object.set_aligned_pointer_in_internal_field(1, ptr) };
+    let ptr = unsafe { object.get_aligned_pointer_from_internal_field(1) };
+    assert_eq!(unsafe { *(ptr as *mut u64) }, 42);
   });
   drop(locker);
 }
 
-extern "C" fn fn_callback(info: &FunctionCallbackInfo) {
-  assert_eq!(info.length(), 0);
-  let isolate = info.get_isolate();
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn object_internal_field_out_of_bounds() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |scope| {
-    let s =
-      v8::String::new(scope, "Hello callback!", v8::NewStringType::Normal)
-        .unwrap();
-    let value: Local<v8::Value> = s.into();
-    let rv = &mut info.get_return_value();
-    let rv_value = rv.get(scope);
-    assert!(rv_value.is_undefined());
-    rv.set(value);
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let null: Local<v8::Value> = new_null(scope).into();
+    let object = v8::Object::new(scope, null, vec![], vec![], 0);
+    assert_eq!(object.internal_field_count(), 0);
+    object.get_internal_field(scope, 0);
   });
+  drop(locker);
 }
 
 #[test]
-fn function() {
-  setup();
+fn object_template() {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  static SET_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  extern "C" fn getter(
+    _name: Local<v8::Name>,
+    info: &v8::PropertyCallbackInfo,
+  ) {
+    let isolate = info.get_isolate();
+    v8::HandleScope::enter(isolate, |scope| {
+      let value = v8::Integer::new(scope, 42);
+      info.get_return_value().set(value.into());
+    });
+  }
+
+  extern "C" fn setter(
+    _name: Local<v8::Name>,
+    value: Local<v8::Value>,
+    _info: &v8::PropertyCallbackInfo,
+  ) {
+    assert!(value.is_number());
+    SET_COUNT.fetch_add(1, Ordering::SeqCst);
+  }
+
+  let _g = setup();
   let mut params = v8::Isolate::create_params();
   params.set_array_buffer_allocator(
     v8::array_buffer::Allocator::new_default_allocator(),
@@ -574,74 +1344,797 @@ fn function() {
   let locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |scope| {
     let mut context = v8::Context::new(scope);
-    context.enter();
-    let global = context.global();
-    let recv: Local<v8::Value> = global.into();
-    // create function using template
-    let mut fn_template = v8::FunctionTemplate::new(scope, fn_callback);
-    let mut function = fn_template
-      .get_function(scope, context)
-      .expect("Unable to create function");
-    let _value =
-      v8::Function::call(&mut *function, scope, context, recv, 0, vec![]);
-    // create function without a template
-    let mut function = v8::Function::new(scope, context, fn_callback)
-      .expect("Unable to create function");
-    let maybe_value =
-      v8::Function::call(&mut *function, scope, context, recv, 0, vec![]);
-    let value = maybe_value.unwrap();
-    let value_str: v8::Local<v8::String> = cast(value);
-    let rust_str = value_str.to_rust_string_lossy(scope);
-    assert_eq!(rust_str, "Hello callback!".to_string());
-    context.exit();
-  });
-  drop(locker);
-}
+    let scope = &mut v8::ContextScope::new(scope, context);
 
-extern "C" fn promise_reject_callback(msg: v8::PromiseRejectMessage) {
-  let event = msg.get_event();
-  assert_eq!(event, v8::PromiseRejectEvent::PromiseRejectWithNoHandler);
-  let mut promise = msg.get_promise();
-  assert_eq!(promise.state(), v8::PromiseState::Rejected);
-  let promise_obj: v8::Local<v8::Object> = cast(promise);
-  let isolate = promise_obj.get_isolate();
-  let value = msg.get_value();
-  let locker = v8::Locker::new(isolate);
-  v8::HandleScope::enter(&isolate, |scope| {
-    let value_str: v8::Local<v8::String> = cast(value);
-    let rust_str = value_str.to_rust_string_lossy(scope);
-    assert_eq!(rust_str, "promise rejected".to_string());
+    let template = v8::ObjectTemplate::new(scope);
+    let name: Local<v8::Name> = v8_str(scope, "constant").into();
+    let value = v8::Integer::new(scope, 1);
+    template.set_with_attr(
+      name,
+      value.into(),
+      v8::PropertyAttribute::READ_ONLY,
+    );
+    let name: Local<v8::Name> = v8_str(scope, "inner").into();
+    let inner = v8::ObjectTemplate::new(scope);
+    template.set(name, inner.into());
+    let name: Local<v8::Name> = v8_str(scope, "getter").into();
+    template.set_accessor(name, getter);
+    let name: Local<v8::Name> = v8_str(scope, "accessor").into();
+    template.set_accessor_with_setter(name, getter, setter);
+    let name: Local<v8::Name> = v8_str(scope, "fn_getter").into();
+    let fn_getter = v8::FunctionTemplate::new(scope, fn_callback);
+    template.set_accessor_property(
+      name,
+      Some(fn_getter),
+      None,
+      v8::PropertyAttribute::NONE,
+    );
+
+    let object = template.new_instance(scope, context).unwrap();
+    let name: Local<v8::Value> = v8_str(scope, "obj").into();
+    context.global().set(context, name, object.into()).unwrap();
+
+    let value = eval(scope, "obj.constant = 2; obj.constant");
+    assert_eq!(value.int32_value(scope, context), Some(1));
+    assert!(eval(scope, "obj.inner").is_object());
+    assert_eq!(
+      eval(scope, "obj.getter").int32_value(scope, context),
+      Some(42)
+    );
+    let value = eval(scope, "obj.accessor = 7; obj.accessor");
+    assert_eq!(value.int32_value(scope, context), Some(42));
+    assert_eq!(SET_COUNT.load(Ordering::SeqCst), 1);
+    let value: Local<v8::String> =
+      eval(scope, "obj.fn_getter").try_into().unwrap();
+    assert_eq!(value.to_rust_string_lossy(scope), "Hello callback!");
   });
   drop(locker);
 }
 
 #[test]
-fn set_promise_reject_callback() {
-  setup();
+fn object_set_accessor() {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  static SET_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  extern "C" fn getter(
+    _name: Local<v8::Name>,
+    info: &v8::PropertyCallbackInfo,
+  ) {
+    let this: Local<v8::Value> = info.this().into();
+    let holder: Local<v8::Value> = info.holder().into();
+    // The accessor is installed on the prototype of the receiver.
+    assert!(!this.strict_equals(holder));
+    info.get_return_value().set(info.data());
+  }
+
+  extern "C" fn setter(
+    _name: Local<v8::Name>,
+    value: Local<v8::Value>,
+    info: &v8::PropertyCallbackInfo,
+  ) {
+    assert!(value.strict_equals(info.data()));
+    SET_COUNT.fetch_add(1, Ordering::SeqCst);
+  }
+
+  let _g = setup();
   let mut params = v8::Isolate::create_params();
   params.set_array_buffer_allocator(
     v8::array_buffer::Allocator::new_default_allocator(),
   );
-  let mut isolate = v8::Isolate::new(params);
-  isolate.set_promise_reject_callback(promise_reject_callback);
-  isolate.enter();
+  let isolate = v8::Isolate::new(params);
   let locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |scope| {
     let mut context = v8::Context::new(scope);
-    context.enter();
-    let mut resolver = v8::PromiseResolver::new(scope, context).unwrap();
-    let str_ =
-      v8::String::new(scope, "promise rejected", v8::NewStringType::Normal)
-        .unwrap();
-    let value: Local<v8::Value> = cast(str_);
-    resolver.reject(context, value);
-    context.exit();
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let null: Local<v8::Value> = new_null(scope).into();
+    let proto = v8::Object::new(scope, null, vec![], vec![], 0);
+    let key: Local<v8::Name> = v8_str(scope, "answer").into();
+    let data: Local<v8::Value> = v8::Integer::new(scope, 42).into();
+    assert_eq!(
+      proto.set_accessor_with_data(context, key, getter, Some(setter), data),
+      Some(true)
+    );
+    let name: Local<v8::Value> = v8_str(scope, "proto").into();
+    context.global().set(context, name, proto.into()).unwrap();
+
+    let value = eval(scope, "Object.create(proto).answer");
+    assert_eq!(value.int32_value(scope, context), Some(42));
+    eval(scope, "proto.answer = 42");
+    assert_eq!(SET_COUNT.load(Ordering::SeqCst), 1);
   });
   drop(locker);
-  isolate.exit();
 }
 
-fn mock_script_origin<'sc>(
+#[test]
+fn object_template_interceptors() {
+  extern "C" fn named_getter(
+    name: Local<v8::Name>,
+    info: &v8::PropertyCallbackInfo,
+  ) {
+    let isolate = info.get_isolate();
+    v8::HandleScope::enter(isolate, |scope| {
+      let home: Local<v8::Value> = v8_str(scope, "HOME").into();
+      if name.strict_equals(home) {
+        let value = v8_str(scope, "/home/user");
+        info.get_return_value().set(value.into());
+      }
+    });
+  }
+
+  extern "C" fn named_setter(
+    name: Local<v8::Name>,
+    value: Local<v8::Value>,
+    info: &v8::PropertyCallbackInfo,
+  ) {
+    let isolate = info.get_isolate();
+    v8::HandleScope::enter(isolate, |scope| {
+      // Swallow all writes to HOME.
+      let home: Local<v8::Value> = v8_str(scope, "HOME").into();
+      if name.strict_equals(home) {
+        info.get_return_value().set(value);
+      }
+    });
+  }
+
+  extern "C" fn named_query(
+    name: Local<v8::Name>,
+    info: &v8::PropertyCallbackInfo<v8::Integer>,
+  ) {
+    let isolate = info.get_isolate();
+    v8::HandleScope::enter(isolate, |scope| {
+      let home: Local<v8::Value> = v8_str(scope, "HOME").into();
+      if name.strict_equals(home) {
+        let attr = v8::PropertyAttribute::READ_ONLY.bits();
+        let value = v8::Integer::new(scope, attr);
+        info.get_return_value().set(value);
+      }
+    });
+  }
+
+  extern "C" fn named_deleter(
+    name: Local<v8::Name>,
+    info: &v8::PropertyCallbackInfo<v8::Boolean>,
+  ) {
+    let isolate = info.get_isolate();
+    v8::HandleScope::enter(isolate, |scope| {
+      let home: Local<v8::Value> = v8_str(scope, "HOME").into();
+      if name.strict_equals(home) {
+        let value = v8::new_false(scope);
+        info.get_return_value().set(value);
+      }
+    });
+  }
+
+  extern "C" fn named_enumerator(info: &v8::PropertyCallbackInfo<v8::Array>) {
+    let isolate = info.get_isolate();
+    v8::HandleScope::enter(isolate, |scope| {
+      let name: Local<v8::Value> = v8_str(scope, "HOME").into();
+      let array = v8::Array::new_with_elements(scope, &[name]);
+      info.get_return_value().set(array);
+    });
+  }
+
+  extern "C" fn indexed_getter(index: u32, info: &v8::PropertyCallbackInfo) {
+    let isolate = info.get_isolate();
+    v8::HandleScope::enter(isolate, |scope| {
+      if index < 3 {
+        let value = v8::Integer::new_from_unsigned(scope, index * 2);
+        info.get_return_value().set(value.into());
+      }
+    });
+  }
+
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let mut context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let template = v8::ObjectTemplate::new(scope);
+    template.set_named_property_handler(
+      v8::NamedPropertyHandlerConfiguration {
+        getter: Some(named_getter),
+        setter: Some(named_setter),
+        query: Some(named_query),
+        deleter: Some(named_deleter),
+        enumerator: Some(named_enumerator),
+      },
+    );
+    template.set_indexed_property_handler(
+      v8::IndexedPropertyHandlerConfiguration {
+        getter: Some(indexed_getter),
+        ..Default::default()
+      },
+    );
+
+    let object = template.new_instance(scope, context).unwrap();
+    let name: Local<v8::Value> = v8_str(scope, "env").into();
+    context.global().set(context, name, object.into()).unwrap();
+
+    let value: Local<v8::String> = eval(scope, "env.HOME").try_into().unwrap();
+    assert_eq!(value.to_rust_string_lossy(scope), "/home/user");
+    let value: Local<v8::String> = eval(scope, "env.HOME = '/tmp'; env.HOME")
+      .try_into()
+      .unwrap();
+    assert_eq!(value.to_rust_string_lossy(scope), "/home/user");
+    assert!(eval(scope, "env.OTHER").is_undefined());
+    let value: Local<v8::String> = eval(scope, "env.OTHER = 'x'; env.OTHER")
+      .try_into()
+      .unwrap();
+    assert_eq!(value.to_rust_string_lossy(scope), "x");
+    assert!(eval(scope, "'HOME' in env").is_true());
+    let value = eval(scope, "Object.getOwnPropertyDescriptor(env, 'HOME')");
+    let descriptor: Local<v8::Object> = value.try_into().unwrap();
+    let writable: Local<v8::Value> = v8_str(scope, "writable").into();
+    let writable = descriptor.get(scope, context, writable).unwrap();
+    assert!(writable.is_false());
+    assert!(eval(scope, "delete env.HOME").is_false());
+    let value: Local<v8::String> =
+      eval(scope, "Object.keys(env).sort().join()")
+        .try_into()
+        .unwrap();
+    assert_eq!(value.to_rust_string_lossy(scope), "HOME,OTHER");
+
+    assert_eq!(eval(scope, "env[2]").int32_value(scope, context), Some(4));
+    assert!(eval(scope, "env[3]").is_undefined());
+  });
+  drop(locker);
+}
+
+#[test]
+fn promise_resolved() {
+  setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let maybe_resolver = v8::PromiseResolver::new(scope, context);
+    assert!(maybe_resolver.is_some());
+    let mut resolver = maybe_resolver.unwrap();
+    let mut promise = resolver.get_promise(scope);
+    assert!(!promise.has_handler());
+    assert_eq!(promise.state(), v8::PromiseState::Pending);
+    let str =
+      v8::String::new(scope, "test", v8::NewStringType::Normal).unwrap();
+    let value: Local<v8::Value> = str.into();
+    resolver.resolve(context, value);
+    assert_eq!(promise.state(), v8::PromiseState::Fulfilled);
+    let result = promise.result(scope);
+    let result_str: v8::Local<v8::String> = result.try_into().unwrap();
+    assert_eq!(result_str.to_rust_string_lossy(scope), "test".to_string());
+    // Resolve again with different value, since promise is already in `Fulfilled` state
+    // it should be ignored.
+    let str =
+      v8::String::new(scope, "test2", v8::NewStringType::Normal).unwrap();
+    let value: Local<v8::Value> = str.into();
+    resolver.resolve(context, value);
+    let result = promise.result(scope);
+    let result_str: v8::Local<v8::String> = result.try_into().unwrap();
+    assert_eq!(result_str.to_rust_string_lossy(scope), "test".to_string());
+  });
+  drop(locker);
+}
+
+#[test]
+fn promise_rejected() {
+  setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let maybe_resolver = v8::PromiseResolver::new(scope, context);
+    assert!(maybe_resolver.is_some());
+    let mut resolver = maybe_resolver.unwrap();
+    let mut promise = resolver.get_promise(scope);
+    assert!(!promise.has_handler());
+    assert_eq!(promise.state(), v8::PromiseState::Pending);
+    let str =
+      v8::String::new(scope, "test", v8::NewStringType::Normal).unwrap();
+    let value: Local<v8::Value> = str.into();
+    let rejected = resolver.reject(context, value);
+    assert!(rejected.unwrap());
+    assert_eq!(promise.state(), v8::PromiseState::Rejected);
+    let result = promise.result(scope);
+    let result_str: v8::Local<v8::String> = result.try_into().unwrap();
+    assert_eq!(result_str.to_rust_string_lossy(scope), "test".to_string());
+    // Reject again with different value, since promise is already in `Rejected` state
+    // it should be ignored.
+    let str =
+      v8::String::new(scope, "test2", v8::NewStringType::Normal).unwrap();
+    let value: Local<v8::Value> = str.into();
+    resolver.reject(context, value);
+    let result = promise.result(scope);
+    let result_str: v8::Local<v8::String> = result.try_into().unwrap();
+    assert_eq!(result_str.to_rust_string_lossy(scope), "test".to_string());
+  });
+  drop(locker);
+}
+
+extern "C" fn fn_callback(info: &FunctionCallbackInfo) {
+  assert_eq!(info.length(), 0);
+  let isolate = info.get_isolate();
+  v8::HandleScope::enter(&isolate, |scope| {
+    let s =
+      v8::String::new(scope, "Hello callback!", v8::NewStringType::Normal)
+        .unwrap();
+    let value: Local<v8::Value> = s.into();
+    let rv = &mut info.get_return_value();
+    let rv_value = rv.get(scope);
+    assert!(rv_value.is_undefined());
+    rv.set(value);
+  });
+}
+
+#[test]
+fn function() {
+  setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let mut context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let global = context.global();
+    let recv: Local<v8::Value> = global.into();
+    // create function using template
+    let fn_template = v8::FunctionTemplate::new(scope, fn_callback);
+    let function = fn_template
+      .get_function(scope, context)
+      .expect("Unable to create function");
+    let _value = function.call(scope, context, recv, &[]);
+    // create function without a template
+    let function = v8::Function::new(scope, context, fn_callback)
+      .expect("Unable to create function");
+    let maybe_value = function.call(scope, context, recv, &[]);
+    let value = maybe_value.unwrap();
+    let value_str: v8::Local<v8::String> = value.try_into().unwrap();
+    let rust_str = value_str.to_rust_string_lossy(scope);
+    assert_eq!(rust_str, "Hello callback!".to_string());
+  });
+  drop(locker);
+}
+
+#[test]
+fn function_callback_info() {
+  extern "C" fn callback(info: &FunctionCallbackInfo) {
+    let isolate = info.get_isolate();
+    v8::HandleScope::enter(isolate, |scope| {
+      assert!(info.data().is_undefined());
+      assert!(info.get_argument(info.length()).is_undefined());
+      let this: Local<v8::Value> = info.this().into();
+      let holder: Local<v8::Value> = info.holder().into();
+      assert!(this.strict_equals(holder));
+      let is_construct_call: Local<v8::Value> = if info.is_construct_call() {
+        v8::new_true(scope).into()
+      } else {
+        v8::new_false(scope).into()
+      };
+      let mut elements = vec![is_construct_call, info.new_target(), this];
+      for i in 0..info.length() {
+        elements.push(info.get_argument(i));
+      }
+      let result = v8::Array::new_with_elements(scope, &elements);
+      info.get_return_value().set(result.into());
+    });
+  }
+
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let mut context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let function = v8::Function::new(scope, context, callback).unwrap();
+    let name: Local<v8::Value> = v8_str(scope, "f").into();
+    context
+      .global()
+      .set(context, name, function.into())
+      .unwrap();
+
+    let value = eval(
+      scope,
+      "var obj = { f }; \
+       var r = obj.f(1, 'a'); \
+       r.length === 5 && r[0] === false && r[1] === undefined && \
+       r[2] === obj && r[3] === 1 && r[4] === 'a'",
+    );
+    assert!(value.is_true());
+    let value = eval(
+      scope,
+      "var r = new f(2); \
+       r.length === 4 && r[0] === true && r[1] === f && \
+       r[2] instanceof f && r[3] === 2",
+    );
+    assert!(value.is_true());
+  });
+  drop(locker);
+}
+
+#[test]
+fn function_closure() {
+  use std::cell::Cell;
+  use std::rc::Rc;
+
+  fn counter(
+    calls: &Rc<Cell<i32>>,
+  ) -> impl Fn(&mut HandleScope, v8::FunctionCallbackArguments, v8::ReturnValue)
+  {
+    let calls = calls.clone();
+    move |scope, args, mut rv| {
+      calls.set(calls.get() + args.length());
+      let count = v8::Integer::new(scope, calls.get());
+      rv.set(count.into());
+    }
+  }
+
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  let calls = Rc::new(Cell::new(0));
+  let collected = Rc::new(Cell::new(0));
+  v8::HandleScope::enter(&isolate, |scope| {
+    let mut context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let function =
+      v8::Function::new_closure(scope, context, counter(&calls)).unwrap();
+    let name: Local<v8::Value> = v8_str(scope, "f").into();
+    context
+      .global()
+      .set(context, name, function.into())
+      .unwrap();
+    let template = v8::FunctionTemplate::new_closure(scope, counter(&calls));
+    let function = template.get_function(scope, context).unwrap();
+    let name: Local<v8::Value> = v8_str(scope, "g").into();
+    context
+      .global()
+      .set(context, name, function.into())
+      .unwrap();
+    assert_eq!(Rc::strong_count(&calls), 3);
+
+    let value = eval(scope, "f(1, 2) === 2 && g(3) === 3 && f() === 3");
+    assert!(value.is_true());
+    assert_eq!(calls.get(), 3);
+
+    // A function that is not reachable from JavaScript frees its closure once
+    // it has been garbage collected.
+    v8::HandleScope::enter(&isolate, |scope| {
+      let calls = calls.clone();
+      let drop_guard = DropCounter(collected.clone());
+      let function =
+        v8::Function::new_closure(scope, context, move |_scope, _args, _rv| {
+          let _ = &drop_guard;
+          calls.set(calls.get() + 1);
+        })
+        .unwrap();
+      let recv = new_null(scope).into();
+      function.call(scope, context, recv, &[]).unwrap();
+    });
+    assert_eq!(calls.get(), 4);
+    assert_eq!(collected.get(), 0);
+    eval(scope, "gc()");
+    assert_eq!(collected.get(), 1);
+    assert_eq!(Rc::strong_count(&calls), 3);
+  });
+  drop(locker);
+
+  // The closures of functions that are still alive are freed when the
+  // isolate is disposed.
+  drop(isolate);
+  assert_eq!(Rc::strong_count(&calls), 1);
+
+  struct DropCounter(Rc<Cell<i32>>);
+  impl Drop for DropCounter {
+    fn drop(&mut self) {
+      self.0.set(self.0.get() + 1);
+    }
+  }
+}
+
+#[test]
+fn function_template_config() {
+  extern "C" fn callback(info: &FunctionCallbackInfo) {
+    info.get_return_value().set(info.data());
+  }
+
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let mut context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let base = v8::FunctionTemplate::new(scope, fn_callback);
+    let key = v8_str(scope, "kind");
+    let value = v8_str(scope, "base");
+    base.prototype_template(scope).set(key.into(), value.into());
+
+    let child = v8::FunctionTemplate::new(scope, fn_callback);
+    let data = v8_str(scope, "tag");
+    child.set_call_handler_with_data(callback, data.into());
+    child.set_class_name(v8_str(scope, "Child"));
+    child.set_length(2);
+    child.inherit(base);
+    child.read_only_prototype();
+    let instance_template = child.instance_template(scope);
+    instance_template.set_internal_field_count(1);
+    let key = v8_str(scope, "x");
+    let value = v8::Integer::new(scope, 1);
+    instance_template.set(key.into(), value.into());
+
+    let no_proto = v8::FunctionTemplate::new(scope, fn_callback);
+    no_proto.remove_prototype();
+
+    for (name, template) in
+      &[("Base", base), ("Child", child), ("NoProto", no_proto)]
+    {
+      let function = template.get_function(scope, context).unwrap();
+      let name: Local<v8::Value> = v8_str(scope, name).into();
+      context
+        .global()
+        .set(context, name, function.into())
+        .unwrap();
+    }
+
+    let value = eval(
+      scope,
+      "Child.length === 2 && Child.name === 'Child' && Child() === 'tag' && \
+       !Object.getOwnPropertyDescriptor(Child, 'prototype').writable && \
+       !NoProto.hasOwnProperty('prototype') && NoProto() === 'Hello callback!'",
+    );
+    assert!(value.is_true());
+
+    let instance = eval(scope, "new Child()");
+    let value = eval(
+      scope,
+      "var c = new Child(); \
+       c instanceof Child && c instanceof Base && c.x === 1 && \
+       c.kind === 'base'",
+    );
+    assert!(value.is_true());
+    let object: Local<v8::Object> = instance.try_into().unwrap();
+    assert_eq!(object.internal_field_count(), 1);
+    assert!(child.has_instance(instance));
+    assert!(!no_proto.has_instance(instance));
+    let plain = eval(scope, "({})");
+    assert!(!child.has_instance(plain));
+  });
+  drop(locker);
+}
+
+#[test]
+fn function_new_instance() {
+  extern "C" fn constructor(info: &FunctionCallbackInfo) {
+    let isolate = info.get_isolate();
+    v8::HandleScope::enter(isolate, |scope| {
+      if info.is_construct_call() {
+        info.this().set_internal_field(0, info.get_argument(0));
+      } else {
+        let value = v8_str(scope, "call");
+        info.get_return_value().set(value.into());
+      }
+    });
+  }
+
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let mut context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let template = v8::FunctionTemplate::new(scope, constructor);
+    template
+      .instance_template(scope)
+      .set_internal_field_count(1);
+    let function = template.get_function(scope, context).unwrap();
+    let name: Local<v8::Value> = v8_str(scope, "Point").into();
+    context
+      .global()
+      .set(context, name, function.into())
+      .unwrap();
+
+    let arg: Local<v8::Value> = v8::Integer::new(scope, 7).into();
+    let object = function.new_instance(scope, context, &[arg]).unwrap();
+    assert!(template.has_instance(object.into()));
+    let field = object.get_internal_field(scope, 0);
+    assert!(field.strict_equals(arg));
+
+    let object: Local<v8::Object> =
+      eval(scope, "new Point('js')").try_into().unwrap();
+    let field = object.get_internal_field(scope, 0);
+    assert!(field.strict_equals(v8_str(scope, "js").into()));
+    let value = eval(scope, "Point(1)");
+    assert!(value.strict_equals(v8_str(scope, "call").into()));
+
+    let function: Local<v8::Function> =
+      eval(scope, "(function(a, b) { this.sum = a + b; })")
+        .try_into()
+        .unwrap();
+    let args: Vec<Local<v8::Value>> = vec![
+      v8::Integer::new(scope, 1).into(),
+      v8::Integer::new(scope, 2).into(),
+    ];
+    let object = function.new_instance(scope, context, &args).unwrap();
+    let key = v8_str(scope, "sum").into();
+    let sum = object.get(scope, context, key).unwrap();
+    assert_eq!(sum.int32_value(scope, context), Some(3));
+
+    let function: Local<v8::Function> =
+      eval(scope, "(() => {})").try_into().unwrap();
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    assert!(function.new_instance(scope, context, &[]).is_none());
+    assert!(tc.has_caught());
+  });
+  drop(locker);
+}
+
+#[test]
+fn function_call_and_metadata() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let function: Local<v8::Function> = eval(
+      scope,
+      "void 0;\n  (function add(a, b) { return this.base + a + b; })",
+    )
+    .try_into()
+    .unwrap();
+    let recv = eval(scope, "({ base: 100 })");
+    let args: Vec<Local<v8::Value>> = vec![
+      v8::Integer::new(scope, 20).into(),
+      v8::Integer::new(scope, 3).into(),
+    ];
+    let result = function.call(scope, context, recv, &args).unwrap();
+    assert_eq!(result.int32_value(scope, context), Some(123));
+
+    let name = function.get_name(scope);
+    assert!(name.strict_equals(v8_str(scope, "add").into()));
+    assert_eq!(function.get_script_line_number(), Some(1));
+    assert_eq!(function.get_script_column_number(), Some(15));
+    assert_ne!(function.script_id(), 0);
+
+    let function = v8::Function::new(scope, context, fn_callback).unwrap();
+    assert_eq!(function.get_script_line_number(), None);
+    assert_eq!(function.get_script_column_number(), None);
+    function.set_name(v8_str(scope, "renamed"));
+    let name = function.get_name(scope);
+    assert!(name.strict_equals(v8_str(scope, "renamed").into()));
+  });
+  drop(locker);
+}
+
+#[test]
+fn return_value_setters() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let mut context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    // The number of arguments selects the setter to use.
+    let function =
+      v8::Function::new_closure(scope, context, |_scope, args, mut rv| {
+        match args.length() {
+          0 => rv.set_int32(-7),
+          1 => rv.set_uint32(4_000_000_000),
+          2 => rv.set_double(0.5),
+          3 => rv.set_bool(true),
+          4 => rv.set_null(),
+          5 => rv.set_undefined(),
+          6 => rv.set_empty_string(),
+          _ => unreachable!(),
+        }
+      })
+      .unwrap();
+    let name: Local<v8::Value> = v8_str(scope, "f").into();
+    context
+      .global()
+      .set(context, name, function.into())
+      .unwrap();
+
+    let value = eval(
+      scope,
+      "f() === -7 && f(0) === 4000000000 && f(0, 0) === 0.5 && \
+       f(0, 0, 0) === true && f(0, 0, 0, 0) === null && \
+       f(0, 0, 0, 0, 0) === undefined && f(0, 0, 0, 0, 0, 0) === ''",
+    );
+    assert!(value.is_true());
+  });
+  drop(locker);
+}
+
+extern "C" fn promise_reject_callback(msg: v8::PromiseRejectMessage) {
+  let event = msg.get_event();
+  assert_eq!(event, v8::PromiseRejectEvent::PromiseRejectWithNoHandler);
+  let mut promise = msg.get_promise();
+  assert_eq!(promise.state(), v8::PromiseState::Rejected);
+  let promise_obj: v8::Local<v8::Object> = promise.into();
+  let isolate = promise_obj.get_isolate();
+  let value = msg.get_value();
+  let locker = v8::Locker::new(isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let value_str: v8::Local<v8::String> = value.try_into().unwrap();
+    let rust_str = value_str.to_rust_string_lossy(scope);
+    assert_eq!(rust_str, "promise rejected".to_string());
+  });
+  drop(locker);
+}
+
+#[test]
+fn set_promise_reject_callback() {
+  setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let mut isolate = v8::Isolate::new(params);
+  isolate.set_promise_reject_callback(promise_reject_callback);
+  let isolate = v8::IsolateScope::new(&mut isolate);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let mut resolver = v8::PromiseResolver::new(scope, context).unwrap();
+    let str_ =
+      v8::String::new(scope, "promise rejected", v8::NewStringType::Normal)
+        .unwrap();
+    let value: Local<v8::Value> = str_.into();
+    resolver.reject(context, value);
+  });
+  drop(locker);
+}
+
+fn mock_script_origin<'sc>(
   scope: &mut HandleScope<'sc>,
 ) -> v8::ScriptOrigin<'sc> {
   let resource_name = v8_str(scope, "foo.js");
@@ -675,11 +2168,11 @@ fn script_compiler_source() {
   );
   let mut isolate = v8::Isolate::new(params);
   isolate.set_promise_reject_callback(promise_reject_callback);
-  isolate.enter();
+  let isolate = v8::IsolateScope::new(&mut isolate);
   let locker = v8::Locker::new(&isolate);
   v8::HandleScope::enter(&isolate, |scope| {
-    let mut context = v8::Context::new(scope);
-    context.enter();
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
 
     let source = "1+2";
     let script_origin = mock_script_origin(scope);
@@ -687,16 +2180,370 @@ fn script_compiler_source() {
       v8::script_compiler::Source::new(v8_str(scope, source), &script_origin);
 
     let result = v8::script_compiler::compile_module(
-      &isolate,
+      scope,
       source,
       v8::script_compiler::CompileOptions::NoCompileOptions,
       v8::script_compiler::NoCacheReason::NoReason,
     );
     assert!(result.is_some());
-
-    context.exit();
   });
   drop(locker);
-  isolate.exit();
   drop(g);
 }
+
+fn compile_module<'sc>(
+  scope: &mut HandleScope<'sc>,
+  source: &str,
+) -> Option<Local<'sc, v8::Module>> {
+  let script_origin = mock_script_origin(scope);
+  let source =
+    v8::script_compiler::Source::new(v8_str(scope, source), &script_origin);
+  v8::script_compiler::compile_module(
+    scope,
+    source,
+    v8::script_compiler::CompileOptions::NoCompileOptions,
+    v8::script_compiler::NoCacheReason::NoReason,
+  )
+}
+
+thread_local! {
+  static MODULE_MAP: std::cell::RefCell<
+    std::collections::HashMap<String, *mut v8::Module>
+  > = Default::default();
+}
+
+extern "C" fn resolve_from_module_map(
+  mut context: Local<v8::Context>,
+  specifier: Local<v8::String>,
+  _referrer: Local<v8::Module>,
+) -> *mut v8::Module {
+  let specifier = specifier.to_rust_string_lossy(context.get_isolate());
+  MODULE_MAP.with(|map| *map.borrow().get(&specifier).unwrap())
+}
+
+#[test]
+fn module_instantiation_and_evaluation() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let mut isolate = v8::Isolate::new(params);
+  let isolate = v8::IsolateScope::new(&mut isolate);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let sources = vec![
+      ("./b.js", "export const b = 21;"),
+      (
+        "./a.js",
+        "import { b } from './b.js'; export const a = b * 2;",
+      ),
+    ];
+    for (specifier, source) in sources {
+      let mut module = compile_module(scope, source).unwrap();
+      MODULE_MAP.with(|map| {
+        map.borrow_mut().insert(specifier.to_string(), &mut *module)
+      });
+    }
+
+    let mut root = compile_module(
+      scope,
+      "import { a } from './a.js';\n\
+       import { b } from './b.js';\n\
+       globalThis.result = a + b;",
+    )
+    .unwrap();
+    assert_eq!(root.get_status(), v8::ModuleStatus::Uninstantiated);
+    assert_eq!(root.get_module_requests_length(), 2);
+    assert_eq!(
+      root
+        .get_module_request(scope, 0)
+        .to_rust_string_lossy(scope),
+      "./a.js"
+    );
+    assert_eq!(
+      root
+        .get_module_request(scope, 1)
+        .to_rust_string_lossy(scope),
+      "./b.js"
+    );
+    assert_ne!(root.get_identity_hash(), 0);
+
+    let result = root.instantiate_module(context, resolve_from_module_map);
+    assert_eq!(result, Some(true));
+    assert_eq!(root.get_status(), v8::ModuleStatus::Instantiated);
+
+    let result = root.evaluate(context);
+    assert!(result.is_some());
+    assert_eq!(root.get_status(), v8::ModuleStatus::Evaluated);
+
+    let source = v8_str(scope, "result");
+    let mut script = v8::Script::compile(scope, source, None).unwrap();
+    let result = script.run(scope).unwrap();
+    let result: Local<v8::Number> = result.try_into().unwrap();
+    assert_eq!(result.value() as i32, 63);
+
+    MODULE_MAP.with(|map| map.borrow_mut().clear());
+  });
+  drop(locker);
+}
+
+#[test]
+fn module_evaluation_error() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let mut isolate = v8::Isolate::new(params);
+  let isolate = v8::IsolateScope::new(&mut isolate);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let mut module = compile_module(scope, "throw new Error('boom');").unwrap();
+    assert_eq!(module.get_module_requests_length(), 0);
+    let result = module.instantiate_module(context, resolve_from_module_map);
+    assert_eq!(result, Some(true));
+    assert!(module.get_exception(scope).is_none());
+
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    assert!(module.evaluate(context).is_none());
+    assert!(tc.has_caught());
+    assert_eq!(module.get_status(), v8::ModuleStatus::Errored);
+    let exception = module.get_exception(scope).unwrap();
+    let message = v8::create_message(scope, exception);
+    assert_eq!(
+      message.get(scope).to_rust_string_lossy(scope),
+      "Uncaught Error: boom"
+    );
+  });
+  drop(locker);
+}
+
+#[test]
+fn module_instantiation_with_closure() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let mut isolate = v8::Isolate::new(params);
+  let isolate = v8::IsolateScope::new(&mut isolate);
+  let locker = v8::Locker::new(&isolate);
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+
+    let mut sources = std::collections::HashMap::new();
+    sources.insert("./a.js", "import { b } from './b.js'; export const a = b;");
+    sources.insert("./b.js", "export const b = 'hello from b';");
+
+    let mut root = compile_module(
+      scope,
+      "import { a } from './a.js';\n\
+       import { b } from './b.js';\n\
+       globalThis.result = a === b ? 42 : 0;",
+    )
+    .unwrap();
+
+    // Modules are compiled lazily, and cached so that './b.js' is only
+    // compiled once even though it is imported twice.
+    let mut module_map = std::collections::HashMap::new();
+    let mut resolve_count = 0;
+    let result = root.instantiate_module_with(
+      context,
+      |_context, specifier, _referrer| {
+        resolve_count += 1;
+        let specifier = specifier.to_rust_string_lossy(scope);
+        if let Some(module) = module_map.get(&specifier) {
+          return Some(*module);
+        }
+        let module = compile_module(scope, sources.get(&*specifier)?)?;
+        module_map.insert(specifier, module);
+        Some(module)
+      },
+    );
+    assert_eq!(result, Some(true));
+    assert_eq!(resolve_count, 3);
+    assert_eq!(module_map.len(), 2);
+    assert_eq!(root.get_status(), v8::ModuleStatus::Instantiated);
+
+    assert!(root.evaluate(context).is_some());
+    let source = v8_str(scope, "result");
+    let mut script = v8::Script::compile(scope, source, None).unwrap();
+    let result = script.run(scope).unwrap();
+    let result: Local<v8::Number> = result.try_into().unwrap();
+    assert_eq!(result.value() as i32, 42);
+  });
+  drop(locker);
+}
+
+#[test]
+fn global_handles() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let mut isolate = v8::Isolate::new(params);
+  let isolate = v8::IsolateScope::new(&mut isolate);
+  let locker = v8::Locker::new(&isolate);
+
+  let mut g1 = v8::Global::<v8::String>::empty();
+  let mut g2 = v8::Global::<v8::Context>::empty();
+  assert!(g1.is_empty());
+  v8::HandleScope::enter(&isolate, |scope| {
+    let s = v8_str(scope, "bla");
+    g1.set(scope, s);
+    let context = v8::Context::new(scope);
+    g2 = v8::Global::new(scope, context);
+  });
+  assert!(!g1.is_empty());
+  assert!(!g2.is_empty());
+
+  // The handles are still valid in an unrelated HandleScope.
+  v8::HandleScope::enter(&isolate, |scope| {
+    let s = g1.get(scope).unwrap();
+    assert_eq!(s.to_rust_string_lossy(scope), "bla");
+    let g3 = v8::Global::new(scope, s);
+    g1.reset();
+    assert!(g1.is_empty());
+    assert!(g1.get(scope).is_none());
+    assert_eq!(g3.get(scope).unwrap().to_rust_string_lossy(scope), "bla");
+
+    let context = g2.get(scope).unwrap();
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let source = v8_str(scope, "'Hello ' + 13 + 'th planet'");
+    let mut script = v8::Script::compile(scope, source, None).unwrap();
+    let result: Local<v8::String> =
+      script.run(scope).unwrap().try_into().unwrap();
+    assert_eq!(result.to_rust_string_lossy(scope), "Hello 13th planet");
+  });
+
+  drop(g2);
+  drop(locker);
+}
+
+#[test]
+fn global_outlives_isolate() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let isolate = v8::Isolate::new(params);
+  let locker = v8::Locker::new(&isolate);
+  let mut global = v8::Global::<v8::String>::empty();
+  v8::HandleScope::enter(&isolate, |scope| {
+    let s = v8_str(scope, "bla");
+    global.set(scope, s);
+  });
+  drop(locker);
+  drop(isolate);
+  // The storage cell was freed together with the isolate.
+  assert!(!global.is_empty());
+  global.reset();
+  assert!(global.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "does not belong to")]
+fn global_used_with_other_isolate() {
+  let _g = setup();
+  let new_isolate = || {
+    let mut params = v8::Isolate::create_params();
+    params.set_array_buffer_allocator(
+      v8::array_buffer::Allocator::new_default_allocator(),
+    );
+    v8::Isolate::new(params)
+  };
+  let isolate1 = new_isolate();
+  let isolate2 = new_isolate();
+  let mut global = v8::Global::<v8::String>::empty();
+  let locker = v8::Locker::new(&isolate1);
+  v8::HandleScope::enter(&isolate1, |scope| {
+    let s = v8_str(scope, "bla");
+    global.set(scope, s);
+  });
+  drop(locker);
+  let _locker = v8::Locker::new(&isolate2);
+  v8::HandleScope::enter(&isolate2, |scope| {
+    global.get(scope);
+  });
+}
+
+#[test]
+fn weak_handles() {
+  let _g = setup();
+  let mut params = v8::Isolate::create_params();
+  params.set_array_buffer_allocator(
+    v8::array_buffer::Allocator::new_default_allocator(),
+  );
+  let mut owned_isolate = v8::Isolate::new(params);
+  let isolate = v8::IsolateScope::new(&mut owned_isolate);
+  let locker = v8::Locker::new(&isolate);
+
+  use std::cell::Cell;
+  use std::rc::Rc;
+  let finalized = Rc::new(Cell::new(0));
+
+  let mut collected = v8::Weak::<v8::Object>::empty();
+  let mut dropped = v8::Weak::<v8::Object>::empty();
+  let mut alive = v8::Weak::<v8::Object>::empty();
+  let mut alive_global = v8::Global::<v8::Object>::empty();
+  v8::HandleScope::enter(&isolate, |scope| {
+    fn new_object<'sc>(scope: &mut HandleScope<'sc>) -> Local<'sc, v8::Object> {
+      let null = new_null(scope).into();
+      v8::Object::new(scope, null, vec![], vec![], 0)
+    }
+    fn finalizer(finalized: &Rc<Cell<i32>>) -> impl FnOnce(&mut v8::Isolate) {
+      let finalized = finalized.clone();
+      move |_| finalized.set(finalized.get() + 1)
+    }
+    let object = new_object(scope);
+    collected = v8::Weak::with_finalizer(scope, object, finalizer(&finalized));
+    let object = new_object(scope);
+    dropped = v8::Weak::with_finalizer(scope, object, finalizer(&finalized));
+    let object = new_object(scope);
+    alive = v8::Weak::with_finalizer(scope, object, finalizer(&finalized));
+    alive_global.set(scope, object);
+  });
+
+  v8::HandleScope::enter(&isolate, |scope| {
+    assert!(!collected.is_empty());
+    assert!(collected.get(scope).is_some());
+  });
+
+  // Dropping a Weak does not cancel its finalizer.
+  drop(dropped);
+
+  v8::HandleScope::enter(&isolate, |scope| {
+    let context = v8::Context::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let source = v8_str(scope, "gc()");
+    let mut script = v8::Script::compile(scope, source, None).unwrap();
+    script.run(scope).unwrap();
+
+    assert_eq!(finalized.get(), 2);
+    assert!(collected.is_empty());
+    assert!(collected.get(scope).is_none());
+    assert!(!alive.is_empty());
+    assert!(alive.get(scope).is_some());
+  });
+
+  // Finalizers of objects that are still alive run when the isolate is
+  // disposed.
+  drop(locker);
+  drop(isolate);
+  drop(owned_isolate);
+  assert_eq!(finalized.get(), 3);
+  assert!(alive.is_empty());
+  drop(alive);
+  drop(alive_global);
+}